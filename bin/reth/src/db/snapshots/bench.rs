@@ -12,6 +12,9 @@ pub(crate) enum BenchKind {
     RandomAll,
     RandomOne,
     RandomHash,
+    Batch,
+    ReusedCursor,
+    FreshCursor,
 }
 
 pub(crate) fn bench<F1, F2, R>(
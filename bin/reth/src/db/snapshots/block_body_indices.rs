@@ -0,0 +1,126 @@
+use super::{
+    bench::{bench, BenchKind},
+    Command, Compression, PerfectHashingFunction,
+};
+use rand::{seq::SliceRandom, Rng};
+use reth_db::{
+    database::Database, models::StoredBlockBodyIndices, open_db_read_only,
+    snapshot::BlockBodyIndicesMask,
+};
+use reth_interfaces::db::LogLevel;
+use reth_primitives::{
+    snapshot::{Filters, InclusionFilter},
+    ChainSpec, SnapshotSegment,
+};
+use reth_provider::{
+    providers::SnapshotProvider, BlockReader, DatabaseProviderRO, ProviderError, ProviderFactory,
+};
+use reth_snapshot::{segments, segments::Segment};
+use std::{path::Path, sync::Arc};
+
+impl Command {
+    pub(crate) fn generate_block_body_indices_snapshot<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRO<'_, DB>,
+        compression: Compression,
+        inclusion_filter: InclusionFilter,
+        phf: PerfectHashingFunction,
+    ) -> eyre::Result<()> {
+        let segment = segments::BlockBodyIndices::new(
+            compression,
+            if self.with_filters {
+                Filters::WithFilters(inclusion_filter, phf)
+            } else {
+                Filters::WithoutFilters
+            },
+        );
+        segment.snapshot::<DB>(provider, self.from..=(self.from + self.block_interval - 1))?;
+
+        Ok(())
+    }
+
+    pub(crate) fn bench_block_body_indices_snapshot(
+        &self,
+        db_path: &Path,
+        log_level: Option<LogLevel>,
+        chain: Arc<ChainSpec>,
+        compression: Compression,
+        inclusion_filter: InclusionFilter,
+        phf: PerfectHashingFunction,
+    ) -> eyre::Result<()> {
+        let filters = if self.with_filters {
+            Filters::WithFilters(inclusion_filter, phf)
+        } else {
+            Filters::WithoutFilters
+        };
+
+        let range = self.from..=(self.from + self.block_interval - 1);
+
+        let mut row_indexes = range.clone().collect::<Vec<_>>();
+        let mut rng = rand::thread_rng();
+        let path = SnapshotSegment::BlockBodyIndices.filename_with_configuration(
+            filters,
+            compression,
+            &range,
+        );
+        let provider = SnapshotProvider::default();
+        let jar_provider = provider.get_segment_provider(
+            SnapshotSegment::BlockBodyIndices,
+            self.from,
+            Some(path),
+        )?;
+        let mut cursor = jar_provider.cursor()?;
+
+        for bench_kind in [BenchKind::Walk, BenchKind::RandomAll] {
+            bench(
+                bench_kind,
+                (open_db_read_only(db_path, log_level)?, chain.clone()),
+                SnapshotSegment::BlockBodyIndices,
+                filters,
+                compression,
+                || {
+                    for num in row_indexes.iter() {
+                        cursor
+                            .get_one::<BlockBodyIndicesMask<StoredBlockBodyIndices>>((*num).into())?
+                            .ok_or(ProviderError::BlockBodyIndicesNotFound(*num))?;
+                    }
+                    Ok(())
+                },
+                |provider| {
+                    for num in row_indexes.iter() {
+                        provider
+                            .block_body_indices(*num)?
+                            .ok_or(ProviderError::BlockBodyIndicesNotFound(*num))?;
+                    }
+                    Ok(())
+                },
+            )?;
+
+            // For random walk
+            row_indexes.shuffle(&mut rng);
+        }
+
+        // BENCHMARK QUERYING A RANDOM BLOCK BODY INDICES BY NUMBER
+        {
+            let num = row_indexes[rng.gen_range(0..row_indexes.len())];
+            bench(
+                BenchKind::RandomOne,
+                (open_db_read_only(db_path, log_level)?, chain.clone()),
+                SnapshotSegment::BlockBodyIndices,
+                filters,
+                compression,
+                || {
+                    Ok(cursor
+                        .get_one::<BlockBodyIndicesMask<StoredBlockBodyIndices>>(num.into())?
+                        .ok_or(ProviderError::BlockBodyIndicesNotFound(num))?)
+                },
+                |provider| {
+                    Ok(provider
+                        .block_body_indices(num)?
+                        .ok_or(ProviderError::BlockBodyIndicesNotFound(num))?)
+                },
+            )?;
+        }
+        Ok(())
+    }
+}
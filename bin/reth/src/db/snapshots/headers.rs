@@ -91,6 +91,40 @@ impl Command {
             row_indexes.shuffle(&mut rng);
         }
 
+        // BENCHMARK POINT QUERIES OF THE PROVIDER, WITH AND WITHOUT REUSING ITS CURSOR PARTS
+        // A new provider has none to reuse, so each of its queries builds its cursor from scratch.
+        for bench_kind in [BenchKind::ReusedCursor, BenchKind::FreshCursor] {
+            let reuse = matches!(bench_kind, BenchKind::ReusedCursor);
+            bench(
+                bench_kind,
+                (open_db_read_only(db_path, log_level)?, chain.clone()),
+                SnapshotSegment::Headers,
+                filters,
+                compression,
+                || {
+                    for num in row_indexes.iter() {
+                        let header = if reuse {
+                            jar_provider.header_by_number(*num)?
+                        } else {
+                            provider
+                                .get_segment_provider(SnapshotSegment::Headers, self.from, None)?
+                                .header_by_number(*num)?
+                        };
+                        header.ok_or(ProviderError::HeaderNotFound((*num).into()))?;
+                    }
+                    Ok(())
+                },
+                |provider| {
+                    for num in row_indexes.iter() {
+                        provider
+                            .header_by_number(*num)?
+                            .ok_or(ProviderError::HeaderNotFound((*num).into()))?;
+                    }
+                    Ok(())
+                },
+            )?;
+        }
+
         // BENCHMARK QUERYING A RANDOM HEADER BY NUMBER
         {
             let num = row_indexes[rng.gen_range(0..row_indexes.len())];
@@ -3,15 +3,17 @@ use itertools::Itertools;
 use reth_db::{open_db_read_only, DatabaseEnvRO};
 use reth_interfaces::db::LogLevel;
 use reth_primitives::{
-    snapshot::{Compression, InclusionFilter, PerfectHashingFunction},
+    snapshot::{Compression, Filters, InclusionFilter, PerfectHashingFunction},
     BlockNumber, ChainSpec, SnapshotSegment,
 };
-use reth_provider::ProviderFactory;
+use reth_provider::{providers::SnapshotProvider, ProviderFactory};
 use std::{path::Path, sync::Arc};
 
 mod bench;
+mod block_body_indices;
 mod headers;
 mod receipts;
+mod senders;
 mod transactions;
 
 #[derive(Parser, Debug)]
@@ -32,6 +34,15 @@ pub struct Command {
     #[arg(long, default_value = "false")]
     bench: bool,
 
+    /// Flag to verify the integrity of the snapshots, after creating them unless `only_bench` or
+    /// `only_verify` is set.
+    #[arg(long, default_value = "false")]
+    verify: bool,
+
+    /// Flag to skip snapshot creation and only verify existing snapshots.
+    #[arg(long, default_value = "false")]
+    only_verify: bool,
+
     /// Flag to skip snapshot creation and only run benchmarks on existing snapshots.
     #[arg(long, default_value = "false")]
     only_bench: bool,
@@ -68,7 +79,7 @@ impl Command {
             let factory = ProviderFactory::new(db, chain.clone());
             let provider = factory.provider()?;
 
-            if !self.only_bench {
+            if !self.only_bench && !self.only_verify {
                 for ((mode, compression), phf) in all_combinations.clone() {
                     match mode {
                         SnapshotSegment::Headers => self
@@ -92,11 +103,31 @@ impl Command {
                                 InclusionFilter::Cuckoo,
                                 *phf,
                             )?,
+                        SnapshotSegment::BlockBodyIndices => self
+                            .generate_block_body_indices_snapshot::<DatabaseEnvRO>(
+                                &provider,
+                                *compression,
+                                InclusionFilter::Cuckoo,
+                                *phf,
+                            )?,
+                        SnapshotSegment::Senders => self
+                            .generate_senders_snapshot::<DatabaseEnvRO>(
+                                &provider,
+                                *compression,
+                                InclusionFilter::Cuckoo,
+                                *phf,
+                            )?,
                     }
                 }
             }
         }
 
+        if self.only_verify || self.verify {
+            for ((mode, compression), phf) in all_combinations.clone() {
+                self.verify_snapshot(*mode, *compression, InclusionFilter::Cuckoo, *phf)?;
+            }
+        }
+
         if self.only_bench || self.bench {
             for ((mode, compression), phf) in all_combinations {
                 match mode {
@@ -124,10 +155,50 @@ impl Command {
                         InclusionFilter::Cuckoo,
                         *phf,
                     )?,
+                    SnapshotSegment::BlockBodyIndices => self.bench_block_body_indices_snapshot(
+                        db_path,
+                        log_level,
+                        chain.clone(),
+                        *compression,
+                        InclusionFilter::Cuckoo,
+                        *phf,
+                    )?,
+                    SnapshotSegment::Senders => self.bench_senders_snapshot(
+                        db_path,
+                        log_level,
+                        chain.clone(),
+                        *compression,
+                        InclusionFilter::Cuckoo,
+                        *phf,
+                    )?,
                 }
             }
         }
 
         Ok(())
     }
+
+    /// Verifies the integrity of an existing `segment` snapshot of the configured block range.
+    fn verify_snapshot(
+        &self,
+        segment: SnapshotSegment,
+        compression: Compression,
+        inclusion_filter: InclusionFilter,
+        phf: PerfectHashingFunction,
+    ) -> eyre::Result<()> {
+        let filters = if self.with_filters {
+            Filters::WithFilters(inclusion_filter, phf)
+        } else {
+            Filters::WithoutFilters
+        };
+
+        let block_range = self.from..=(self.from + self.block_interval - 1);
+        let path = segment.filename_with_configuration(filters, compression, &block_range);
+
+        let provider = SnapshotProvider::default();
+        provider.get_segment_provider(segment, self.from, Some(path.clone()))?.verify()?;
+        println!("{segment:?} snapshot {} verified", path.display());
+
+        Ok(())
+    }
 }
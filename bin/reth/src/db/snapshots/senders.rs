@@ -0,0 +1,128 @@
+use super::{
+    bench::{bench, BenchKind},
+    Command, Compression, PerfectHashingFunction,
+};
+use rand::{seq::SliceRandom, Rng};
+use reth_db::{database::Database, open_db_read_only, snapshot::SenderMask};
+use reth_interfaces::db::LogLevel;
+use reth_primitives::{
+    snapshot::{Filters, InclusionFilter},
+    Address, ChainSpec, SnapshotSegment,
+};
+use reth_provider::{
+    providers::SnapshotProvider, DatabaseProviderRO, ProviderError, ProviderFactory,
+    TransactionsProvider, TransactionsProviderExt,
+};
+use reth_snapshot::{segments, segments::Segment};
+use std::{path::Path, sync::Arc};
+
+impl Command {
+    pub(crate) fn generate_senders_snapshot<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRO<'_, DB>,
+        compression: Compression,
+        inclusion_filter: InclusionFilter,
+        phf: PerfectHashingFunction,
+    ) -> eyre::Result<()> {
+        let segment = segments::Senders::new(
+            compression,
+            if self.with_filters {
+                Filters::WithFilters(inclusion_filter, phf)
+            } else {
+                Filters::WithoutFilters
+            },
+        );
+        segment.snapshot::<DB>(provider, self.from..=(self.from + self.block_interval - 1))?;
+
+        Ok(())
+    }
+
+    pub(crate) fn bench_senders_snapshot(
+        &self,
+        db_path: &Path,
+        log_level: Option<LogLevel>,
+        chain: Arc<ChainSpec>,
+        compression: Compression,
+        inclusion_filter: InclusionFilter,
+        phf: PerfectHashingFunction,
+    ) -> eyre::Result<()> {
+        let filters = if self.with_filters {
+            Filters::WithFilters(inclusion_filter, phf)
+        } else {
+            Filters::WithoutFilters
+        };
+
+        let block_range = self.from..=(self.from + self.block_interval - 1);
+
+        let mut rng = rand::thread_rng();
+
+        let tx_range = ProviderFactory::new(open_db_read_only(db_path, log_level)?, chain.clone())
+            .provider()?
+            .transaction_range_by_block_range(block_range.clone())?;
+
+        let mut row_indexes = tx_range.clone().collect::<Vec<_>>();
+
+        let path = SnapshotSegment::Senders.filename_with_configuration(
+            filters,
+            compression,
+            &block_range,
+        );
+        let provider = SnapshotProvider::default();
+        let jar_provider =
+            provider.get_segment_provider(SnapshotSegment::Senders, self.from, Some(path))?;
+        let mut cursor = jar_provider.cursor()?;
+
+        for bench_kind in [BenchKind::Walk, BenchKind::RandomAll] {
+            bench(
+                bench_kind,
+                (open_db_read_only(db_path, log_level)?, chain.clone()),
+                SnapshotSegment::Senders,
+                filters,
+                compression,
+                || {
+                    for num in row_indexes.iter() {
+                        cursor
+                            .get_one::<SenderMask<Address>>((*num).into())?
+                            .ok_or(ProviderError::TransactionNotFound((*num).into()))?;
+                    }
+                    Ok(())
+                },
+                |provider| {
+                    for num in row_indexes.iter() {
+                        provider
+                            .transaction_sender(*num)?
+                            .ok_or(ProviderError::TransactionNotFound((*num).into()))?;
+                    }
+                    Ok(())
+                },
+            )?;
+
+            // For random walk
+            row_indexes.shuffle(&mut rng);
+        }
+
+        // BENCHMARK QUERYING A RANDOM SENDER BY NUMBER
+        {
+            let num = row_indexes[rng.gen_range(0..row_indexes.len())];
+            bench(
+                BenchKind::RandomOne,
+                (open_db_read_only(db_path, log_level)?, chain.clone()),
+                SnapshotSegment::Senders,
+                filters,
+                compression,
+                || {
+                    Ok(cursor
+                        .get_one::<SenderMask<Address>>(num.into())?
+                        .ok_or(ProviderError::TransactionNotFound(num.into()))?)
+                },
+                |provider| {
+                    Ok(provider
+                        .transaction_sender(num as u64)?
+                        .ok_or(ProviderError::TransactionNotFound((num as u64).into()))?)
+                },
+            )?;
+        }
+
+        Ok(())
+    }
+}
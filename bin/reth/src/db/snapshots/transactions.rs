@@ -102,6 +102,34 @@ impl Command {
             row_indexes.shuffle(&mut rng);
         }
 
+        // BENCHMARK QUERYING ALL TRANSACTIONS IN A SINGLE BATCH, TO COMPARE WITH THE WALK
+        {
+            let numbers = tx_range.clone().collect::<Vec<_>>();
+            bench(
+                BenchKind::Batch,
+                (open_db_read_only(db_path, log_level)?, chain.clone()),
+                SnapshotSegment::Transactions,
+                filters,
+                compression,
+                || {
+                    let txs =
+                        cursor.get_many::<TransactionMask<TransactionSignedNoHash>>(&numbers)?;
+                    for (num, tx) in numbers.iter().zip(txs) {
+                        tx.ok_or(ProviderError::TransactionNotFound((*num).into()))?.with_hash();
+                    }
+                    Ok(())
+                },
+                |provider| {
+                    for num in numbers.iter() {
+                        provider
+                            .transaction_by_id(*num)?
+                            .ok_or(ProviderError::TransactionNotFound((*num).into()))?;
+                    }
+                    Ok(())
+                },
+            )?;
+        }
+
         // BENCHMARK QUERYING A RANDOM TRANSACTION BY NUMBER
         {
             let num = row_indexes[rng.gen_range(0..row_indexes.len())];
@@ -1,6 +1,8 @@
 use reth_primitives::{
-    Address, BlockHash, BlockHashOrNumber, BlockNumber, TxHashOrNumber, TxNumber, B256,
+    Address, BlockHash, BlockHashOrNumber, BlockNumber, SnapshotSegment, TxHashOrNumber, TxNumber,
+    B256,
 };
+use std::ops::RangeInclusive;
 
 /// Bundled errors variants thrown by various providers.
 #[derive(Debug, thiserror::Error, PartialEq, Eq, Clone)]
@@ -111,4 +113,54 @@ pub enum ProviderError {
     /// State is not available for the given block number because it is pruned.
     #[error("state at block #{0} is pruned")]
     StateAtBlockPruned(BlockNumber),
+    /// Provider does not support this particular request.
+    #[error("this provider does not support this request")]
+    UnsupportedProvider,
+    /// The snapshot segment required by this request is not available to the provider.
+    #[error("missing snapshot segment {0:?}")]
+    MissingSnapshotSegment(SnapshotSegment),
+    /// A snapshot can't be the auxiliar of another one.
+    #[error("{auxiliar:?} snapshot can't be an auxiliar of the {segment:?} snapshot: {reason}")]
+    IncompatibleSnapshotAuxiliar {
+        /// The segment of the snapshot the auxiliar was added to.
+        segment: SnapshotSegment,
+        /// The segment of the auxiliar snapshot.
+        auxiliar: SnapshotSegment,
+        /// Why they're not compatible.
+        reason: String,
+    },
+    /// A range was requested with a step of zero.
+    #[error("range step must be greater than zero")]
+    ZeroRangeStep,
+    /// A snapshot failed its integrity verification.
+    #[error("{segment:?} snapshot is corrupted at #{number}: {reason}")]
+    CorruptedSnapshot {
+        /// The snapshot segment.
+        segment: SnapshotSegment,
+        /// The block or transaction number of the first row failing verification.
+        number: u64,
+        /// Why the row failed verification.
+        reason: String,
+    },
+    /// The requested blocks or transactions fall outside the range covered by the snapshot, so
+    /// they have to be queried on another one.
+    #[error("{segment:?} snapshot covers {covered:?}, but {requested:?} was requested")]
+    SnapshotOutOfRange {
+        /// The snapshot segment.
+        segment: SnapshotSegment,
+        /// The requested block or transaction numbers.
+        requested: RangeInclusive<u64>,
+        /// The block or transaction numbers covered by the snapshot.
+        covered: RangeInclusive<u64>,
+    },
+    /// A number covered by two snapshot segments is only present in one of them.
+    #[error("#{number} is present in the {present:?} snapshot, but missing from the {missing:?} snapshot")]
+    SnapshotSegmentsMisaligned {
+        /// The block or transaction number.
+        number: u64,
+        /// The segment holding it.
+        present: SnapshotSegment,
+        /// The segment missing it.
+        missing: SnapshotSegment,
+    },
 }
@@ -14,6 +14,10 @@ pub enum SnapshotSegment {
     Transactions,
     /// Snapshot segment responsible for the `Receipts` table.
     Receipts,
+    /// Snapshot segment responsible for the `BlockBodyIndices` table.
+    BlockBodyIndices,
+    /// Snapshot segment responsible for the `TxSenders` table.
+    Senders,
 }
 
 impl SnapshotSegment {
@@ -28,6 +32,8 @@ impl SnapshotSegment {
             SnapshotSegment::Headers => default_config,
             SnapshotSegment::Transactions => default_config,
             SnapshotSegment::Receipts => default_config,
+            SnapshotSegment::BlockBodyIndices => default_config,
+            SnapshotSegment::Senders => default_config,
         }
     }
 
@@ -48,6 +54,8 @@ impl SnapshotSegment {
             SnapshotSegment::Headers => "headers",
             SnapshotSegment::Transactions => "transactions",
             SnapshotSegment::Receipts => "receipts",
+            SnapshotSegment::BlockBodyIndices => "block_body_indices",
+            SnapshotSegment::Senders => "senders",
         };
         let filters_name = match filters {
             Filters::WithFilters(inclusion_filter, phf) => {
@@ -99,6 +107,16 @@ impl SegmentHeader {
         Self { block_range, tx_range, segment }
     }
 
+    /// Returns the block range of the segment.
+    pub fn block_range(&self) -> &RangeInclusive<BlockNumber> {
+        &self.block_range
+    }
+
+    /// Returns the transaction range of the segment.
+    pub fn tx_range(&self) -> &RangeInclusive<TxNumber> {
+        &self.tx_range
+    }
+
     /// Returns the first block number of the segment.
     pub fn block_start(&self) -> BlockNumber {
         *self.block_range.start()
@@ -109,11 +127,18 @@ impl SegmentHeader {
         *self.tx_range.start()
     }
 
+    /// Returns the segment type.
+    pub fn segment(&self) -> SnapshotSegment {
+        self.segment
+    }
+
     /// Returns the row offset which depends on whether the segment is block or transaction based.
     pub fn start(&self) -> u64 {
         match self.segment {
-            SnapshotSegment::Headers => self.block_start(),
-            SnapshotSegment::Transactions | SnapshotSegment::Receipts => self.tx_start(),
+            SnapshotSegment::Headers | SnapshotSegment::BlockBodyIndices => self.block_start(),
+            SnapshotSegment::Transactions |
+            SnapshotSegment::Receipts |
+            SnapshotSegment::Senders => self.tx_start(),
         }
     }
 }
@@ -7,7 +7,6 @@ use alloy_rlp::{
 };
 use bytes::{Buf, BytesMut};
 use derive_more::{AsRef, Deref};
-use once_cell::sync::Lazy;
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
 use reth_codecs::{add_arbitrary_tests, derive_arbitrary, Compact};
 use serde::{Deserialize, Serialize};
@@ -45,13 +44,35 @@ pub(crate) mod util;
 mod variant;
 
 // Expected number of transactions where we can expect a speed-up by recovering the senders in
-// parallel.
-pub(crate) static PARALLEL_SENDER_RECOVERY_THRESHOLD: Lazy<usize> =
-    Lazy::new(|| match rayon::current_num_threads() {
+// parallel. It depends on the threads of the current rayon pool, so it's evaluated on each
+// recovery instead of once for the global pool.
+pub(crate) fn parallel_sender_recovery_threshold() -> usize {
+    match rayon::current_num_threads() {
         0..=1 => usize::MAX,
         2..=8 => 10,
         _ => 5,
-    });
+    }
+}
+
+/// Recovers the signers of `txes` with `recover_signer`, in parallel once there are enough of
+/// them to benefit from it.
+///
+/// Returns `None`, if some transaction's signature is invalid.
+fn recover_signers<'a, Tx, T>(
+    txes: T,
+    num_txes: usize,
+    recover_signer: fn(&Tx) -> Option<Address>,
+) -> Option<Vec<Address>>
+where
+    Tx: Sync + 'a,
+    T: IntoParallelIterator<Item = &'a Tx> + IntoIterator<Item = &'a Tx> + Send,
+{
+    if num_txes < parallel_sender_recovery_threshold() {
+        txes.into_iter().map(recover_signer).collect()
+    } else {
+        txes.into_par_iter().map(recover_signer).collect()
+    }
+}
 
 /// A raw transaction.
 ///
@@ -726,6 +747,17 @@ impl TransactionSignedNoHash {
     pub fn with_hash(self) -> TransactionSigned {
         self.into()
     }
+
+    /// Recovers a list of signers from a transaction list iterator
+    ///
+    /// Returns `None`, if some transaction's signature is invalid, see also
+    /// [Self::recover_signer].
+    pub fn recover_signers<'a, T>(txes: T, num_txes: usize) -> Option<Vec<Address>>
+    where
+        T: IntoParallelIterator<Item = &'a Self> + IntoIterator<Item = &'a Self> + Send,
+    {
+        recover_signers(txes, num_txes, Self::recover_signer)
+    }
 }
 
 impl Compact for TransactionSignedNoHash {
@@ -868,11 +900,7 @@ impl TransactionSigned {
     where
         T: IntoParallelIterator<Item = &'a Self> + IntoIterator<Item = &'a Self> + Send,
     {
-        if num_txes < *PARALLEL_SENDER_RECOVERY_THRESHOLD {
-            txes.into_iter().map(|tx| tx.recover_signer()).collect()
-        } else {
-            txes.into_par_iter().map(|tx| tx.recover_signer()).collect()
-        }
+        recover_signers(txes, num_txes, Self::recover_signer)
     }
 
     /// Consumes the type, recover signer and return [`TransactionSignedEcRecovered`]
@@ -1315,8 +1343,8 @@ mod tests {
     use crate::{
         hex, sign_message,
         transaction::{
-            signature::Signature, TransactionKind, TxEip1559, TxLegacy,
-            PARALLEL_SENDER_RECOVERY_THRESHOLD,
+            parallel_sender_recovery_threshold, signature::Signature, TransactionKind, TxEip1559,
+            TxLegacy,
         },
         Address, Bytes, Transaction, TransactionSigned, TransactionSignedEcRecovered, B256, U256,
     };
@@ -1560,7 +1588,7 @@ mod tests {
         #![proptest_config(proptest::prelude::ProptestConfig::with_cases(1))]
 
         #[test]
-        fn test_parallel_recovery_order(txes in proptest::collection::vec(proptest::prelude::any::<Transaction>(), *PARALLEL_SENDER_RECOVERY_THRESHOLD * 5)) {
+        fn test_parallel_recovery_order(txes in proptest::collection::vec(proptest::prelude::any::<Transaction>(), parallel_sender_recovery_threshold() * 5)) {
             let mut rng =rand::thread_rng();
             let secp = Secp256k1::new();
             let txes: Vec<TransactionSigned> = txes.into_iter().map(|mut tx| {
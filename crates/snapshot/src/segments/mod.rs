@@ -9,6 +9,12 @@ pub use headers::Headers;
 mod receipts;
 pub use receipts::Receipts;
 
+mod block_body_indices;
+pub use block_body_indices::BlockBodyIndices;
+
+mod senders;
+pub use senders::Senders;
+
 use reth_db::{
     cursor::DbCursorRO, database::Database, table::Table, transaction::DbTx, RawKey, RawTable,
 };
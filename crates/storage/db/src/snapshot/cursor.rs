@@ -18,6 +18,55 @@ impl<'a> SnapshotCursor<'a> {
         Ok(Self(NippyJarCursor::with_handle(jar, mmap_handle)?))
     }
 
+    /// Returns a new [`SnapshotCursor`] which decompresses into `buffer`, reusing its allocation.
+    /// Meant for the parts returned by [`Self::into_parts`].
+    pub fn with_buffer(
+        jar: &'a NippyJar<SegmentHeader>,
+        mmap_handle: MmapHandle,
+        buffer: Vec<u8>,
+    ) -> Result<Self, RethError> {
+        Ok(Self(NippyJarCursor::with_buffer(jar, mmap_handle, buffer)?))
+    }
+
+    /// Consumes the cursor, returning its mmap handle and buffer so that another cursor of the
+    /// same segment can be built from them.
+    pub fn into_parts(self) -> (MmapHandle, Vec<u8>) {
+        self.0.into_parts()
+    }
+
+    /// Returns the current `BlockNumber` or `TxNumber` of the cursor depending on the kind of
+    /// snapshot segment. This is the number of the row the cursor points at, which is the next one
+    /// to be read.
+    ///
+    /// Its value after each operation is:
+    /// * a new cursor: the first number of the segment.
+    /// * a query returning a row: the number following the returned row, since reading advances
+    ///   past it. For key queries, it's the row the key was mapped to, which may not hold that key.
+    /// * a query by number beyond the last row: the queried number.
+    /// * a query by number before the first row, or by a key rejected by the filter: unchanged.
+    /// * a seek: the sought number, or the first number of the segment if it was before it.
+    pub fn number(&self) -> u64 {
+        self.row_index() + self.jar().user_header().start()
+    }
+
+    /// Moves the cursor to `number`, so that it's the next one read by [`Self::next_one`].
+    /// Numbers before the first one of the segment move it to the first one instead.
+    pub fn seek(&mut self, number: u64) {
+        let offset = self.jar().user_header().start();
+        self.set_row_index(number.saturating_sub(offset));
+    }
+
+    /// Gets one column value from the row the cursor points at, alongside its number, and
+    /// advances to the next one. Returns `None` once the end of the segment is reached.
+    pub fn next_one<M: ColumnSelectorOne>(&mut self) -> RethResult<Option<(u64, M::FIRST)>> {
+        let number = self.number();
+
+        match self.next_row_with_cols(M::MASK)? {
+            Some(row) => Ok(Some((number, M::FIRST::decompress(row[0])?))),
+            None => Ok(None),
+        }
+    }
+
     /// Gets a row of values.
     pub fn get(
         &mut self,
@@ -51,6 +100,52 @@ impl<'a> SnapshotCursor<'a> {
         }
     }
 
+    /// Gets one column value from each row of `numbers`, returned in the same order. Values of
+    /// numbers missing from the segment are `None`.
+    ///
+    /// Rows are read in ascending order in a single pass with [`Self::get_many_with`].
+    pub fn get_many<M: ColumnSelectorOne>(
+        &mut self,
+        numbers: &[u64],
+    ) -> RethResult<Vec<Option<M::FIRST>>> {
+        let mut values = Vec::with_capacity(numbers.len());
+        values.resize_with(numbers.len(), || None);
+
+        let mut order = (0..numbers.len()).collect::<Vec<_>>();
+        order.sort_unstable_by_key(|&i| numbers[i]);
+
+        // Rows before the start of the segment are skipped without being read
+        let first = order.partition_point(|&i| numbers[i] < self.jar().user_header().start());
+        let mut positions = order[first..].iter();
+        self.get_many_with::<M>(order[first..].iter().map(|&i| numbers[i]), |_, value| {
+            values[*positions.next().expect("a position for each row")] = Some(value);
+            Ok(())
+        })?;
+
+        Ok(values)
+    }
+
+    /// Gets one column value from each row of the ascending `numbers`, and passes it to `f`
+    /// alongside its number, without collecting them. Stops at the first number beyond the end of
+    /// the segment, returning how many rows were read.
+    ///
+    /// Numbers before the start of the segment are skipped. What's shared by all the rows is
+    /// prepared once instead of on each [`Self::get_one`]. See
+    /// [`NippyJarCursor::rows_with_cols`].
+    pub fn get_many_with<M: ColumnSelectorOne>(
+        &mut self,
+        numbers: impl IntoIterator<Item = u64>,
+        mut f: impl FnMut(u64, M::FIRST) -> RethResult<()>,
+    ) -> RethResult<usize> {
+        let offset = self.jar().user_header().start();
+
+        self.rows_with_cols(
+            numbers.into_iter().filter_map(|num| num.checked_sub(offset)).map(|row| row as usize),
+            M::MASK,
+            |row, values| f(row as u64 + offset, M::FIRST::decompress(values[0])?),
+        )
+    }
+
     /// Gets two column values from a row.
     pub fn get_two<M: ColumnSelectorTwo>(
         &mut self,
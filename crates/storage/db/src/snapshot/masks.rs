@@ -1,9 +1,9 @@
-use super::{ReceiptMask, TransactionMask};
+use super::{BlockBodyIndicesMask, ReceiptMask, SenderMask, TransactionMask};
 use crate::{
     add_snapshot_mask,
     snapshot::mask::{ColumnSelectorOne, ColumnSelectorTwo, HeaderMask},
     table::Table,
-    CanonicalHeaders, HeaderTD, Receipts, Transactions,
+    BlockBodyIndices, CanonicalHeaders, HeaderTD, Receipts, Transactions, TxSenders,
 };
 use reth_primitives::{BlockHash, Header};
 
@@ -14,6 +14,7 @@ add_snapshot_mask!(HeaderMask, <HeaderTD as Table>::Value, 0b010);
 add_snapshot_mask!(HeaderMask, BlockHash, 0b100);
 
 add_snapshot_mask!(HeaderMask, Header, BlockHash, 0b101);
+add_snapshot_mask!(HeaderMask, Header, <HeaderTD as Table>::Value, 0b011);
 add_snapshot_mask!(
     HeaderMask,
     <HeaderTD as Table>::Value,
@@ -26,3 +27,9 @@ add_snapshot_mask!(ReceiptMask, <Receipts as Table>::Value, 0b1);
 
 // TRANSACTION MASKS
 add_snapshot_mask!(TransactionMask, <Transactions as Table>::Value, 0b1);
+
+// BLOCK BODY INDICES MASKS
+add_snapshot_mask!(BlockBodyIndicesMask, <BlockBodyIndices as Table>::Value, 0b1);
+
+// SENDER MASKS
+add_snapshot_mask!(SenderMask, <TxSenders as Table>::Value, 0b1);
@@ -18,6 +18,8 @@ pub struct NippyJarCursor<'a, H = ()> {
     internal_buffer: Vec<u8>,
     /// Cursor row position.
     row: u64,
+    /// Number of rows read by the cursor since it was created.
+    rows_read: u64,
 }
 
 impl<'a, H: std::fmt::Debug> std::fmt::Debug for NippyJarCursor<'a, H>
@@ -41,6 +43,7 @@ where
             // Makes sure that we have enough buffer capacity to decompress any row of data.
             internal_buffer: Vec::with_capacity(max_row_size),
             row: 0,
+            rows_read: 0,
         })
     }
 
@@ -55,18 +58,105 @@ where
             // Makes sure that we have enough buffer capacity to decompress any row of data.
             internal_buffer: Vec::with_capacity(max_row_size),
             row: 0,
+            rows_read: 0,
         })
     }
 
+    /// Returns a new cursor which decompresses into `buffer`, reusing its allocation. Meant for
+    /// the parts returned by [`Self::into_parts`], so that nothing is allocated or acquired again.
+    pub fn with_buffer(
+        jar: &'a NippyJar<H>,
+        mmap_handle: MmapHandle,
+        mut buffer: Vec<u8>,
+    ) -> Result<Self, NippyJarError> {
+        buffer.clear();
+        // Makes sure that we have enough buffer capacity to decompress any row of data.
+        buffer.reserve(jar.max_row_size);
+        Ok(NippyJarCursor { jar, mmap_handle, internal_buffer: buffer, row: 0, rows_read: 0 })
+    }
+
+    /// Consumes the cursor, returning its mmap handle and internal buffer so that another cursor
+    /// of the same jar can be built from them with [`Self::with_buffer`].
+    pub fn into_parts(self) -> (MmapHandle, Vec<u8>) {
+        (self.mmap_handle, self.internal_buffer)
+    }
+
     pub fn jar(&self) -> &NippyJar<H> {
         self.jar
     }
 
+    /// Returns the index of the row the cursor points at, which is the next one to be read.
+    pub fn row_index(&self) -> u64 {
+        self.row
+    }
+
+    /// Returns how many rows the cursor has read since it was created, whether by number, by key
+    /// or in batches.
+    pub fn rows_read(&self) -> u64 {
+        self.rows_read
+    }
+
     /// Resets cursor to the beginning.
     pub fn reset(&mut self) {
         self.row = 0;
     }
 
+    /// Moves the cursor to the row at index `row`, so that it's the next one read by
+    /// [`Self::next_row`] and [`Self::next_row_with_cols`].
+    pub fn set_row_index(&mut self, row: u64) {
+        self.row = row;
+    }
+
+    /// Hints the operating system that the data of `rows` is about to be read sequentially, so
+    /// that it's read ahead instead of faulted in one page at a time.
+    ///
+    /// It's best-effort: it does nothing on non-Unix platforms and failures are ignored, since
+    /// reads are unaffected by them.
+    pub fn prefetch_rows(&self, rows: Range<usize>) {
+        let columns = self.jar.columns;
+        let (start, end) = (rows.start * columns, rows.end * columns);
+        if rows.is_empty() || start >= self.jar.offsets.len() {
+            return
+        }
+
+        let data_start = self.jar.offsets.select(start).expect("should exist");
+        let data_end = if end >= self.jar.offsets.len() {
+            self.mmap_handle.len()
+        } else {
+            self.jar.offsets.select(end).expect("should exist")
+        };
+
+        #[cfg(unix)]
+        for advice in [memmap2::Advice::Sequential, memmap2::Advice::WillNeed] {
+            let _ = self.mmap_handle.advise_range(advice, data_start, data_end - data_start);
+        }
+        #[cfg(not(unix))]
+        let _ = (data_start, data_end);
+    }
+
+    /// Checks that every column value of `row` has an offset and is stored within the data file.
+    /// Reading a row failing it would go out of bounds.
+    pub fn check_row_bounds(&self, row: usize) -> Result<(), NippyJarError> {
+        for column in 0..self.jar.columns {
+            let offset_pos = row * self.jar.columns + column;
+            if offset_pos >= self.jar.offsets.len() {
+                return Err(NippyJarError::UnexpectedMissingValue(row as u64, column as u64))
+            }
+
+            let value_end = if self.jar.offsets.len() == offset_pos + 1 {
+                self.mmap_handle.len()
+            } else {
+                self.jar.offsets.select(offset_pos + 1).expect("should exist")
+            };
+            let value_start = self.jar.offsets.select(offset_pos).expect("should exist");
+            if value_start > value_end || value_end > self.mmap_handle.len() {
+                return Err(NippyJarError::OffsetOutOfBounds(row as u64, column as u64))
+            }
+        }
+
+        Ok(())
+    }
+
     /// Returns a row, searching it by a key used during [`NippyJar::prepare_index`].
     ///
     /// **May return false positives.**
@@ -119,6 +209,7 @@ where
         }
 
         self.row += 1;
+        self.rows_read += 1;
 
         Ok(Some(
             row.into_iter()
@@ -194,6 +285,7 @@ where
             }
         }
         self.row += 1;
+        self.rows_read += 1;
 
         Ok(Some(
             row.into_iter()
@@ -205,40 +297,146 @@ where
         ))
     }
 
-    /// Takes the column index and reads the range value for the corresponding column.
-    fn read_value(
+    /// Returns a row by its number without decompressing its column values, by using a `mask` to
+    /// only read certain columns from the row.
+    ///
+    /// Column values are returned exactly as stored in the data file. If the jar has no
+    /// compression, this is equivalent to [`Self::row_by_number_with_cols`].
+    pub fn raw_row_by_number_with_cols(
         &mut self,
-        column: usize,
-        row: &mut Vec<ValueRange>,
-    ) -> Result<(), NippyJarError> {
+        row: usize,
+        mask: usize,
+    ) -> Result<Option<RefRow<'_>>, NippyJarError> {
+        self.row = row as u64;
+
+        if self.row as usize * self.jar.columns >= self.jar.offsets.len() {
+            // Has reached the end
+            return Ok(None)
+        }
+
+        let ranges = (0..self.jar.columns)
+            .filter(|column| mask & (1 << column) != 0)
+            .map(|column| self.column_value_range(column))
+            .collect::<Vec<_>>();
+        self.row += 1;
+        self.rows_read += 1;
+
+        Ok(Some(ranges.into_iter().map(|range| &self.mmap_handle[range]).collect()))
+    }
+
+    /// Returns the range of the stored column value of the current row on the data file.
+    fn column_value_range(&self, column: usize) -> Range<usize> {
         // Find out the offset of the column value
         let offset_pos = self.row as usize * self.jar.columns + column;
         let value_offset = self.jar.offsets.select(offset_pos).expect("should exist");
 
-        let column_offset_range = if self.jar.offsets.len() == (offset_pos + 1) {
+        if self.jar.offsets.len() == (offset_pos + 1) {
             // It's the last column of the last row
             value_offset..self.mmap_handle.len()
         } else {
             let next_value_offset = self.jar.offsets.select(offset_pos + 1).expect("should exist");
             value_offset..next_value_offset
+        }
+    }
+
+    /// Reads the values of the columns selected by `mask` of each row in `rows`, in the given
+    /// order, and calls `f` with the row and its values. Stops at the first row beyond the end of
+    /// the jar, returning how many rows were read.
+    ///
+    /// Equivalent to calling [`Self::row_by_number_with_cols`] for each row, but the work that
+    /// doesn't depend on the row is only done once for all of them: dictionary decompressors are
+    /// prepared once per column instead of once per value, and the buffer of value ranges is
+    /// reused.
+    pub fn rows_with_cols<E: From<NippyJarError>>(
+        &mut self,
+        rows: impl IntoIterator<Item = usize>,
+        mask: usize,
+        mut f: impl FnMut(usize, Vec<&[u8]>) -> Result<(), E>,
+    ) -> Result<usize, E> {
+        let jar = self.jar;
+        let mut decompressors = match jar.compressor() {
+            Some(Compressors::Zstd(zstd)) if zstd.use_dict => zstd.decompressors()?,
+            _ => vec![],
         };
+        let columns =
+            (0..jar.columns).filter(|column| mask & (1 << column) != 0).collect::<Vec<_>>();
+        let mut ranges = Vec::with_capacity(columns.len());
+        let mut read = 0;
+
+        for row in rows {
+            if row * jar.columns >= jar.offsets.len() {
+                // Has reached the end
+                break
+            }
+
+            self.row = row as u64;
+            self.internal_buffer.clear();
+            ranges.clear();
+            for &column in &columns {
+                self.read_value_with(column, decompressors.get_mut(column), &mut ranges)?;
+            }
+            self.row += 1;
+            self.rows_read += 1;
+
+            f(
+                row,
+                ranges
+                    .iter()
+                    .map(|value| match value {
+                        ValueRange::Mmap(range) => &self.mmap_handle[range.clone()],
+                        ValueRange::Internal(range) => &self.internal_buffer[range.clone()],
+                    })
+                    .collect(),
+            )?;
+            read += 1;
+        }
+
+        Ok(read)
+    }
+
+    /// Takes the column index and reads the range value for the corresponding column.
+    fn read_value(
+        &mut self,
+        column: usize,
+        row: &mut Vec<ValueRange>,
+    ) -> Result<(), NippyJarError> {
+        self.read_value_with(column, None, row)
+    }
+
+    /// Same as [`Self::read_value`], decompressing with `decompressor` if the jar uses dictionaries
+    /// instead of preparing a new one.
+    fn read_value_with(
+        &mut self,
+        column: usize,
+        decompressor: Option<&mut Decompressor<'a>>,
+        row: &mut Vec<ValueRange>,
+    ) -> Result<(), NippyJarError> {
+        let column_offset_range = self.column_value_range(column);
 
-        if let Some(compression) = self.jar.compressor() {
+        let jar = self.jar;
+        if let Some(compression) = jar.compressor() {
             let from = self.internal_buffer.len();
             match compression {
                 Compressors::Zstd(z) if z.use_dict => {
                     // If we are here, then for sure we have the necessary dictionaries and they're
                     // loaded (happens during deserialization). Otherwise, there's an issue
                     // somewhere else and we can't recover here anyway.
-                    let dictionaries = z.dictionaries.as_ref().expect("dictionaries to exist")
-                        [column]
-                        .loaded()
-                        .expect("dictionary to be loaded");
-                    let mut decompressor = Decompressor::with_prepared_dictionary(dictionaries)?;
+                    let mut prepared;
+                    let decompressor = match decompressor {
+                        Some(decompressor) => decompressor,
+                        None => {
+                            let dictionaries =
+                                z.dictionaries.as_ref().expect("dictionaries to exist")[column]
+                                    .loaded()
+                                    .expect("dictionary to be loaded");
+                            prepared = Decompressor::with_prepared_dictionary(dictionaries)?;
+                            &mut prepared
+                        }
+                    };
                     Zstd::decompress_with_dictionary(
                         &self.mmap_handle[column_offset_range],
                         &mut self.internal_buffer,
-                        &mut decompressor,
+                        decompressor,
                     )?;
                 }
                 _ => {
@@ -21,6 +21,8 @@ pub enum NippyJarError {
     ColumnLenMismatch(usize, usize),
     #[error("unexpected missing value: row:col {0}:{1}")]
     UnexpectedMissingValue(u64, u64),
+    #[error("value out of the data file bounds: row:col {0}:{1}")]
+    OffsetOutOfBounds(u64, u64),
     #[error(transparent)]
     FilterError(#[from] cuckoofilter::CuckooError),
     #[error("nippy jar initialized without filter")]
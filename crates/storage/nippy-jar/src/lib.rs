@@ -201,6 +201,19 @@ where
         &self.user_header
     }
 
+    /// Returns the number of data columns.
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Returns the number of rows, as given by the offsets index.
+    pub fn rows(&self) -> usize {
+        if self.columns == 0 {
+            return 0
+        }
+        self.offsets.len() / self.columns
+    }
+
     /// Gets a reference to the compressor.
     pub fn compressor(&self) -> Option<&Compressors> {
         self.compressor.as_ref()
@@ -994,4 +1007,41 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_raw_column_values() {
+        let (col1, col2) = test_data(None);
+        let num_rows = col1.len() as u64;
+        let num_columns = 2;
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+
+        let mut nippy = NippyJar::new_without_header(num_columns, file_path.path()).with_lz4();
+        nippy.freeze(vec![clone_with_result(&col1), clone_with_result(&col2)], num_rows).unwrap();
+
+        let loaded_nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        assert_eq!(loaded_nippy.rows(), num_rows as usize);
+        let compressor = loaded_nippy.compressor().expect("has compressor");
+        let mut cursor = NippyJarCursor::new(&loaded_nippy).unwrap();
+
+        for (row_num, (v0, v1)) in col1.iter().zip(col2.iter()).enumerate() {
+            let raw_row = cursor
+                .raw_row_by_number_with_cols(row_num, 0b11)
+                .unwrap()
+                .unwrap()
+                .iter()
+                .map(|a| a.to_vec())
+                .collect::<Vec<_>>();
+
+            // Raw values are stored compressed
+            assert_eq!(compressor.decompress(&raw_row[0]).unwrap(), *v0);
+            assert_eq!(compressor.decompress(&raw_row[1]).unwrap(), *v1);
+
+            // Only selected columns are returned
+            let raw_row = cursor.raw_row_by_number_with_cols(row_num, 0b10).unwrap().unwrap();
+            assert_eq!(raw_row.len(), 1);
+            assert_eq!(compressor.decompress(raw_row[0]).unwrap(), *v1);
+        }
+
+        assert!(cursor.raw_row_by_number_with_cols(num_rows as usize, 0b11).unwrap().is_none());
+    }
 }
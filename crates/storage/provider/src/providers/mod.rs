@@ -37,7 +37,7 @@ mod bundle_state_provider;
 mod chain_info;
 mod database;
 mod snapshot;
-pub use snapshot::{SnapshotJarProvider, SnapshotProvider};
+pub use snapshot::{SnapshotJarCursor, SnapshotJarProvider, SnapshotProvider};
 mod state;
 use crate::{providers::chain_info::ChainInfoTracker, traits::BlockSource};
 pub use bundle_state_provider::BundleStateProvider;
@@ -1,237 +1,5032 @@
-use super::LoadedJarRef;
-use crate::{BlockHashReader, BlockNumReader, HeaderProvider, TransactionsProvider};
+use super::{LoadedJar, SnapshotJarMetrics};
+use crate::{
+    BlockHashReader, BlockNumReader, HeaderProvider, ReceiptProvider, TransactionsProvider,
+};
+use parking_lot::Mutex;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use reth_db::{
     codecs::CompactU256,
-    snapshot::{HeaderMask, SnapshotCursor},
+    models::StoredBlockBodyIndices,
+    snapshot::{
+        BlockBodyIndicesMask, ColumnSelectorOne, HeaderMask, ReceiptMask, SenderMask,
+        SnapshotCursor, TransactionMask,
+    },
     table::Decompress,
 };
-use reth_interfaces::{provider::ProviderError, RethResult};
+use reth_interfaces::{
+    executor::{BlockExecutionError, BlockValidationError},
+    provider::ProviderError,
+    RethError, RethResult,
+};
+use reth_nippy_jar::MmapHandle;
 use reth_primitives::{
-    Address, BlockHash, BlockHashOrNumber, BlockNumber, ChainInfo, Header, SealedHeader,
-    TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, B256, U256,
+    constants::EMPTY_WITHDRAWALS, Address, BlockHash, BlockHashOrNumber, BlockNumber, Bloom,
+    BloomInput, Bytes, ChainInfo, Header, Log, Receipt, SealedBlock, SealedHeader, SnapshotSegment,
+    TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, B256,
+    EMPTY_OMMER_ROOT_HASH, U256,
 };
-use std::ops::{Deref, Range, RangeBounds};
+use std::{
+    cmp::Ordering,
+    marker::PhantomData,
+    ops::{Deref, DerefMut, Range, RangeBounds, RangeInclusive},
+    sync::Arc,
+};
+
+/// Ranges of headers shorter than this are read sequentially by
+/// [`SnapshotJarProvider::headers_range_par`], since splitting them costs more than it saves.
+const HEADERS_PAR_MIN_RANGE: u64 = 10_000;
 
 /// Provider over a specific `NippyJar` and range.
 #[derive(Debug)]
-pub struct SnapshotJarProvider<'a>(LoadedJarRef<'a>);
+pub struct SnapshotJarProvider<'a> {
+    /// Main snapshot segment. It's shared with the manager entry it was obtained from until the
+    /// entry is reloaded, which leaves this provider with the jar it had.
+    jar: Arc<LoadedJar>,
+    /// Auxiliar snapshot segments, consulted for data the main jar doesn't hold. Eg. the block
+    /// body indices required to find the transactions of a block.
+    auxiliar_jars: Vec<Self>,
+    /// Mmap handle and decompression buffer of the last dropped cursor, from which the next one is
+    /// rebuilt. The cursor itself can't be kept, since it borrows the jar owned by this provider,
+    /// but this is all of its state that's worth reusing: its row is set by every query.
+    cursor_parts: Mutex<Option<(MmapHandle, Vec<u8>)>>,
+    /// Providers are obtained from a [`SnapshotProvider`](super::SnapshotProvider) and don't
+    /// outlive it.
+    _manager: PhantomData<&'a ()>,
+}
 
 impl<'a> Deref for SnapshotJarProvider<'a> {
-    type Target = LoadedJarRef<'a>;
+    type Target = LoadedJar;
+    fn deref(&self) -> &Self::Target {
+        &self.jar
+    }
+}
+
+impl<'a> From<Arc<LoadedJar>> for SnapshotJarProvider<'a> {
+    fn from(value: Arc<LoadedJar>) -> Self {
+        SnapshotJarProvider {
+            jar: value,
+            auxiliar_jars: Vec::new(),
+            cursor_parts: Mutex::new(None),
+            _manager: PhantomData,
+        }
+    }
+}
+
+/// [`SnapshotCursor`] of a [`SnapshotJarProvider`], which hands its parts back to the provider and
+/// records the rows it read once dropped.
+#[derive(Debug)]
+pub struct SnapshotJarCursor<'a> {
+    /// Always `Some`, until dropped.
+    cursor: Option<SnapshotCursor<'a>>,
+    /// Cursor parts slot of the provider.
+    parts: &'a Mutex<Option<(MmapHandle, Vec<u8>)>>,
+    /// Metrics of the provider jar.
+    metrics: &'a SnapshotJarMetrics,
+}
+
+impl<'a> Deref for SnapshotJarCursor<'a> {
+    type Target = SnapshotCursor<'a>;
     fn deref(&self) -> &Self::Target {
-        &self.0
+        self.cursor.as_ref().expect("exists until dropped")
     }
 }
 
-impl<'a> From<LoadedJarRef<'a>> for SnapshotJarProvider<'a> {
-    fn from(value: LoadedJarRef<'a>) -> Self {
-        SnapshotJarProvider(value)
+impl<'a> DerefMut for SnapshotJarCursor<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.cursor.as_mut().expect("exists until dropped")
+    }
+}
+
+impl<'a> Drop for SnapshotJarCursor<'a> {
+    fn drop(&mut self) {
+        if let Some(cursor) = self.cursor.take() {
+            // Every row read goes through the cursor, whatever the query
+            self.metrics.record_rows_decoded(cursor.rows_read() as usize);
+
+            let mut parts = self.parts.lock();
+            if parts.is_none() {
+                *parts = Some(cursor.into_parts());
+            }
+        }
+    }
+}
+
+/// Jars are compared by the range of blocks they cover, regardless of their segment.
+impl<'a> PartialEq for SnapshotJarProvider<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.block_range() == other.block_range()
+    }
+}
+
+/// A jar is ordered before another if all of its blocks come before theirs. Overlapping jars with
+/// different ranges are not comparable.
+impl<'a> PartialOrd for SnapshotJarProvider<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let (range, other) = (self.block_range(), other.block_range());
+        if range == other {
+            Some(Ordering::Equal)
+        } else if range.end <= other.start {
+            Some(Ordering::Less)
+        } else if other.end <= range.start {
+            Some(Ordering::Greater)
+        } else {
+            None
+        }
     }
 }
 
 impl<'a> SnapshotJarProvider<'a> {
     /// Provides a cursor for more granular data access.
-    pub fn cursor<'b>(&'b self) -> RethResult<SnapshotCursor<'a>>
-    where
-        'b: 'a,
-    {
-        SnapshotCursor::new(self.value(), self.mmap_handle())
+    ///
+    /// Sequential cursors of the same provider are built from the same parts: a dropped cursor
+    /// hands back its mmap handle and decompression buffer, so that the next one doesn't acquire
+    /// or allocate them again. Single queries like [`HeaderProvider::header_by_number`] create a
+    /// cursor each, so they go through the same one in turn. Cursors alive at the same time are
+    /// independent.
+    ///
+    /// The cursor borrows the provider rather than the jar, so it can be obtained through any
+    /// reference to it, like one held by a wrapper:
+    ///
+    /// ```
+    /// use reth_db::snapshot::HeaderMask;
+    /// use reth_interfaces::RethResult;
+    /// use reth_primitives::{BlockNumber, Header};
+    /// use reth_provider::providers::SnapshotJarProvider;
+    ///
+    /// struct HeadersReader<'a> {
+    ///     jar: SnapshotJarProvider<'a>,
+    /// }
+    ///
+    /// impl<'a> HeadersReader<'a> {
+    ///     fn header(&self, number: BlockNumber) -> RethResult<Option<Header>> {
+    ///         let mut cursor = self.jar.cursor()?;
+    ///         cursor.get_one::<HeaderMask<Header>>(number.into())
+    ///     }
+    /// }
+    /// ```
+    pub fn cursor(&self) -> RethResult<SnapshotJarCursor<'_>> {
+        self.metrics().record_cursor();
+        let cursor = match self.cursor_parts.lock().take() {
+            Some((mmap_handle, buffer)) => {
+                SnapshotCursor::with_buffer(&self.jar, mmap_handle, buffer)
+            }
+            None => SnapshotCursor::new(&self.jar, self.mmap_handle()),
+        }?;
+        Ok(SnapshotJarCursor {
+            cursor: Some(cursor),
+            parts: &self.cursor_parts,
+            metrics: self.metrics(),
+        })
     }
-}
 
-impl<'a> HeaderProvider for SnapshotJarProvider<'a> {
-    fn header(&self, block_hash: &BlockHash) -> RethResult<Option<Header>> {
-        Ok(self
-            .cursor()?
-            .get_two::<HeaderMask<Header, BlockHash>>(block_hash.into())?
-            .filter(|(_, hash)| hash == block_hash)
-            .map(|(header, _)| header))
+    /// Returns the generation of the jar within its [`SnapshotProvider`](super::SnapshotProvider),
+    /// which changes whenever the jar is reloaded.
+    ///
+    /// Data derived from this provider can be checked for staleness with
+    /// [`SnapshotProvider::is_stale`](super::SnapshotProvider::is_stale).
+    pub fn epoch(&self) -> u64 {
+        self.jar.epoch()
     }
 
-    fn header_by_number(&self, num: BlockNumber) -> RethResult<Option<Header>> {
-        self.cursor()?.get_one::<HeaderMask<Header>>(num.into())
+    /// Returns the half-open range of blocks covered by this jar: `start..end`, where `end` is the
+    /// number following its last block.
+    pub fn block_range(&self) -> Range<BlockNumber> {
+        to_half_open(self.user_header().block_range())
     }
 
-    fn header_td(&self, block_hash: &BlockHash) -> RethResult<Option<U256>> {
-        Ok(self
-            .cursor()?
-            .get_two::<HeaderMask<CompactU256, BlockHash>>(block_hash.into())?
-            .filter(|(_, hash)| hash == block_hash)
-            .map(|(td, _)| td.into()))
+    /// Returns the half-open range of transactions covered by this jar: `start..end`, where `end`
+    /// is the number following its last transaction. It's empty if the jar covers none, like one
+    /// of blocks without transactions.
+    pub fn tx_range(&self) -> Range<TxNumber> {
+        to_half_open(self.user_header().tx_range())
     }
 
-    fn header_td_by_number(&self, num: BlockNumber) -> RethResult<Option<U256>> {
-        Ok(self.cursor()?.get_one::<HeaderMask<CompactU256>>(num.into())?.map(Into::into))
+    /// Returns the inclusive range of blocks covered by this jar: `start..=last`.
+    ///
+    /// Same start as [`Self::block_range`], with the end being the last block instead of the one
+    /// following it.
+    pub fn block_range_inclusive(&self) -> RangeInclusive<BlockNumber> {
+        self.user_header().block_range().clone()
     }
 
-    fn headers_range(&self, range: impl RangeBounds<BlockNumber>) -> RethResult<Vec<Header>> {
+    /// Returns how many rows of `range` are present in the jar, without reading any of them.
+    ///
+    /// Rows are stored contiguously from the jar start, so this is the overlap between `range` and
+    /// the rows of the offsets index. Anything short of the range length means it has gaps.
+    pub fn present_count_in_range(&self, range: impl RangeBounds<u64>) -> RethResult<u64> {
+        let range = self.present_range(to_range(range));
+        Ok(range.end - range.start)
+    }
+
+    /// Returns the part of `range` whose rows are present in the jar, which holds them contiguously
+    /// from its start. It's empty if they don't overlap.
+    ///
+    /// Range methods clamp to it before reading, so that unbounded ranges return exactly what the
+    /// jar holds, without sizing anything by their bounds.
+    fn present_range(&self, range: Range<u64>) -> Range<u64> {
+        let start = self.user_header().start();
+        let end = start.saturating_add(self.rows() as u64);
+
+        let range_start = range.start.max(start);
+        range_start..range.end.min(end).max(range_start)
+    }
+
+    /// Returns the range of numbers covered by the jar according to its metadata: blocks for the
+    /// headers and block body indices segments, transactions otherwise.
+    fn covered_range(&self) -> RangeInclusive<u64> {
+        let header = self.user_header();
+        match header.segment() {
+            SnapshotSegment::Headers | SnapshotSegment::BlockBodyIndices => {
+                header.block_range().clone()
+            }
+            SnapshotSegment::Transactions |
+            SnapshotSegment::Receipts |
+            SnapshotSegment::Senders => header.tx_range().clone(),
+        }
+    }
+
+    /// Returns [`ProviderError::SnapshotOutOfRange`] if `range` is not empty and falls entirely
+    /// outside the range covered by the jar, meaning it has to be queried on another one.
+    ///
+    /// Numbers within it that are not found are genuine misses, reported as such by each method.
+    fn ensure_covered(&self, range: &Range<u64>) -> RethResult<()> {
+        let covered = self.covered_range();
+        if range.is_empty() || (range.start <= *covered.end() && *covered.start() < range.end) {
+            return Ok(())
+        }
+
+        Err(ProviderError::SnapshotOutOfRange {
+            segment: self.user_header().segment(),
+            requested: range.start..=range.end - 1,
+            covered,
+        }
+        .into())
+    }
+
+    /// Returns [`ProviderError::SnapshotOutOfRange`] if `num` falls outside the range covered by
+    /// the jar.
+    ///
+    /// Every query of a single number starts with it, so it's also where they're recorded.
+    fn ensure_covered_number(&self, num: u64) -> RethResult<()> {
+        self.metrics().record_point_query();
+        self.ensure_covered(&(num..num.saturating_add(1)))
+    }
+
+    /// Checks that `range` is covered by the jar with [`Self::ensure_covered`], returning its part
+    /// present in the jar with [`Self::present_range`].
+    ///
+    /// Range queries start with it, so it's also where they're recorded.
+    fn query_range(&self, range: impl RangeBounds<u64>) -> RethResult<Range<u64>> {
         let range = to_range(range);
+        self.ensure_covered(&range)?;
+        let present = self.present_range(range);
+        self.metrics().record_range_query(present.end - present.start);
+        Ok(present)
+    }
+
+    /// Reads a value for each of `numbers` in order with a single cursor, stopping at the first
+    /// one `f` doesn't find. Capacity is reserved for at most the rows of the jar, and no cursor
+    /// is created if there are no numbers, as for empty or inverted ranges.
+    fn collect_range<T>(
+        &self,
+        numbers: impl Iterator<Item = u64>,
+        mut f: impl FnMut(&mut SnapshotCursor<'_>, u64) -> RethResult<Option<T>>,
+    ) -> RethResult<Vec<T>> {
+        self.collect_range_batch(numbers, |cursor, numbers, values| {
+            for num in numbers {
+                match f(cursor, num)? {
+                    Some(value) => values.push(value),
+                    None => break,
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Same as [`Self::collect_range`], but `f` is given all of `numbers` at once to read them as
+    /// a batch, pushing their values into the output.
+    fn collect_range_batch<T, I: Iterator<Item = u64>>(
+        &self,
+        numbers: I,
+        f: impl FnOnce(&mut SnapshotCursor<'_>, I, &mut Vec<T>) -> RethResult<()>,
+    ) -> RethResult<Vec<T>> {
+        if numbers.size_hint().1 == Some(0) {
+            return Ok(Vec::new())
+        }
 
         let mut cursor = self.cursor()?;
-        let mut headers = Vec::with_capacity((range.end - range.start) as usize);
+        let mut values = Vec::with_capacity(numbers.size_hint().0.min(self.rows()));
+        f(&mut *cursor, numbers, &mut values)?;
 
-        for num in range.start..range.end {
-            match cursor.get_one::<HeaderMask<Header>>(num.into())? {
-                Some(header) => headers.push(header),
-                None => return Ok(headers),
+        Ok(values)
+    }
+
+    /// Hints the operating system that the rows of `range` present in the jar are about to be read
+    /// sequentially, so that they're read ahead from disk. Meant to be called before scanning large
+    /// ranges.
+    ///
+    /// It's best-effort: it does nothing on non-Unix platforms and failures to give the hint are
+    /// ignored, since reads are unaffected by them.
+    pub fn prefetch_range(&self, range: impl RangeBounds<u64>) -> RethResult<()> {
+        let offset = self.user_header().start();
+        let range = self.present_range(to_range(range));
+
+        self.cursor()?
+            .prefetch_rows((range.start - offset) as usize..(range.end - offset) as usize);
+        Ok(())
+    }
+
+    /// Returns `true` if this is the base jar, which starts at genesis.
+    pub fn is_base(&self) -> bool {
+        self.user_header().block_start() == 0
+    }
+
+    /// Returns the genesis block hash if this is the base jar, otherwise `None`.
+    pub fn genesis_hash(&self) -> RethResult<Option<B256>> {
+        if !self.is_base() {
+            return Ok(None)
+        }
+        self.block_hash(0)
+    }
+
+    /// Returns `true` if the block `num` falls within the range covered by this jar.
+    ///
+    /// Only consults the jar metadata, so it's a cheap way to pick the jar of a block before
+    /// querying it.
+    pub fn contains_block(&self, num: BlockNumber) -> bool {
+        self.block_range().contains(&num)
+    }
+
+    /// Returns `true` if the transaction `num` falls within the range covered by this jar.
+    ///
+    /// Only consults the jar metadata, so it's a cheap way to pick the jar of a transaction before
+    /// querying it.
+    pub fn contains_tx(&self, num: TxNumber) -> bool {
+        self.tx_range().contains(&num)
+    }
+
+    /// Returns the number of headers held by the jar, or zero if it's not a headers jar.
+    ///
+    /// Like the other counts, it's read from the jar metadata, without reading any row.
+    pub fn num_headers(&self) -> u64 {
+        self.num_rows_of(SnapshotSegment::Headers)
+    }
+
+    /// Returns the number of transactions held by the jar, or zero if it's not a transactions jar.
+    pub fn num_transactions(&self) -> u64 {
+        self.num_rows_of(SnapshotSegment::Transactions)
+    }
+
+    /// Returns the number of receipts held by the jar, or zero if it's not a receipts jar.
+    pub fn num_receipts(&self) -> u64 {
+        self.num_rows_of(SnapshotSegment::Receipts)
+    }
+
+    /// Returns the number of rows of the jar if it's a `segment` jar, or zero otherwise.
+    fn num_rows_of(&self, segment: SnapshotSegment) -> u64 {
+        if self.user_header().segment() == segment {
+            self.rows() as u64
+        } else {
+            0
+        }
+    }
+
+    /// Compares the range covered by this jar against the block `num`: [`Ordering::Less`] if the
+    /// jar only covers earlier blocks, [`Ordering::Greater`] if it only covers later ones and
+    /// [`Ordering::Equal`] if it contains it.
+    ///
+    /// Allows finding the jar of a block with `binary_search_by` over jars sorted by their ranges,
+    /// which is only valid as long as none of them overlap.
+    pub fn cmp_block(&self, num: BlockNumber) -> Ordering {
+        let range = self.block_range();
+        if range.end <= num {
+            Ordering::Less
+        } else if num < range.start {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
+
+    /// Adds a new auxiliar snapshot segment, keeping any previous ones.
+    ///
+    /// Auxiliars are picked by their segment whenever a request needs data this jar doesn't hold.
+    /// If several share a segment, the first one added is used, unless the request looks for the
+    /// one covering a given number, like [`ReceiptProvider::receipt_by_hash`].
+    pub fn with_auxiliar(mut self, auxiliar_jar: SnapshotJarProvider<'a>) -> Self {
+        self.auxiliar_jars.push(auxiliar_jar);
+        self
+    }
+
+    /// Adds a new auxiliar snapshot segment like [`Self::with_auxiliar`], after checking that it's
+    /// compatible with this jar and its previous auxiliars.
+    ///
+    /// Returns [`ProviderError::IncompatibleSnapshotAuxiliar`] if:
+    /// * its columns are not the ones of its segment, so its metadata can't be trusted.
+    /// * its block range doesn't overlap with the one of this jar, so it holds data of other
+    ///   blocks.
+    /// * it's of the same segment as this jar or a previous auxiliar, and the numbers they cover
+    ///   overlap. Only one of them would be read, so they can only extend each other's coverage.
+    pub fn try_with_auxiliar(self, auxiliar_jar: SnapshotJarProvider<'a>) -> RethResult<Self> {
+        let segment = self.user_header().segment();
+        let auxiliar = auxiliar_jar.user_header().segment();
+        let incompatible = |reason: String| -> RethResult<Self> {
+            Err(ProviderError::IncompatibleSnapshotAuxiliar { segment, auxiliar, reason }.into())
+        };
+
+        let columns = segment_columns(auxiliar);
+        if auxiliar_jar.columns() != columns {
+            return incompatible(format!(
+                "expected {columns} columns, found {}",
+                auxiliar_jar.columns()
+            ))
+        }
+
+        let (blocks, auxiliar_blocks) =
+            (self.block_range_inclusive(), auxiliar_jar.block_range_inclusive());
+        if auxiliar_blocks.start() > blocks.end() || blocks.start() > auxiliar_blocks.end() {
+            return incompatible(format!(
+                "it covers blocks {auxiliar_blocks:?}, but the snapshot covers {blocks:?}"
+            ))
+        }
+
+        let covered = auxiliar_jar.covered_range();
+        if let Some(jar) = self.segment_providers(auxiliar).find(|jar| {
+            let other = jar.covered_range();
+            covered.start() <= other.end() && other.start() <= covered.end()
+        }) {
+            return incompatible(format!(
+                "it covers {covered:?}, which overlaps with the {:?} covered by another one",
+                jar.covered_range()
+            ))
+        }
+
+        Ok(self.with_auxiliar(auxiliar_jar))
+    }
+
+    /// Returns the provider of `segment` data, which is either this jar or one of its auxiliars.
+    ///
+    /// Returns [`ProviderError::UnsupportedProvider`] if none of them is of the requested segment.
+    fn segment_provider(&self, segment: SnapshotSegment) -> RethResult<&Self> {
+        if self.user_header().segment() == segment {
+            return Ok(self)
+        }
+
+        self.auxiliar_jars
+            .iter()
+            .find(|jar| jar.user_header().segment() == segment)
+            .ok_or_else(|| ProviderError::UnsupportedProvider.into())
+    }
+
+    /// Returns the providers of `segment` data among this jar and its auxiliars, in the order
+    /// they're consulted.
+    fn segment_providers(&self, segment: SnapshotSegment) -> impl Iterator<Item = &Self> {
+        std::iter::once(self)
+            .chain(&self.auxiliar_jars)
+            .filter(move |jar| jar.user_header().segment() == segment)
+    }
+
+    /// Returns the provider of `segment` data covering `num`, among this jar and its auxiliars, or
+    /// `None` if none of them covers it.
+    ///
+    /// Returns [`ProviderError::UnsupportedProvider`] if none of them is of the requested segment.
+    fn segment_provider_covering(
+        &self,
+        segment: SnapshotSegment,
+        num: u64,
+    ) -> RethResult<Option<&Self>> {
+        let mut providers = self.segment_providers(segment).peekable();
+        if providers.peek().is_none() {
+            return Err(ProviderError::UnsupportedProvider.into())
+        }
+        Ok(providers.find(|jar| jar.covered_range().contains(&num)))
+    }
+
+    /// Returns the [`StoredBlockBodyIndices`] of a block, which requires the block body indices
+    /// segment.
+    pub fn block_body_indices(
+        &self,
+        num: BlockNumber,
+    ) -> RethResult<Option<StoredBlockBodyIndices>> {
+        let provider = self.segment_provider(SnapshotSegment::BlockBodyIndices)?;
+        provider.ensure_covered_number(num)?;
+        provider.cursor()?.get_one::<BlockBodyIndicesMask<StoredBlockBodyIndices>>(num.into())
+    }
+
+    /// Verifies that the header timestamps in `range` are monotonic, returning the numbers of the
+    /// blocks whose timestamp is not greater than their parent's one. If `strict` is `false`, equal
+    /// timestamps are allowed and only decreasing ones are reported.
+    ///
+    /// The range is clamped to the headers of the jar, and the first block verified is compared
+    /// against its parent if this jar holds it, otherwise it has no predecessor and is never
+    /// reported. Returns [`ProviderError::SnapshotOutOfRange`] if `range` falls entirely outside
+    /// the jar, instead of reporting no violations.
+    pub fn verify_timestamp_monotonicity(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+        strict: bool,
+    ) -> RethResult<Vec<BlockNumber>> {
+        let range = self.query_range(range)?;
+        let mut cursor = self.cursor()?;
+
+        let mut parent_timestamp = match range.start.checked_sub(1) {
+            Some(parent) => {
+                cursor.get_one::<HeaderMask<Header>>(parent.into())?.map(|header| header.timestamp)
+            }
+            None => None,
+        };
+
+        let mut violations = Vec::new();
+        for number in range {
+            let Some(header) = cursor.get_one::<HeaderMask<Header>>(number.into())? else { break };
+
+            if let Some(parent_timestamp) = parent_timestamp {
+                let is_monotonic = if strict {
+                    header.timestamp > parent_timestamp
+                } else {
+                    header.timestamp >= parent_timestamp
+                };
+
+                if !is_monotonic {
+                    violations.push(number);
+                }
             }
+
+            parent_timestamp = Some(header.timestamp);
         }
 
-        Ok(headers)
+        Ok(violations)
     }
 
-    fn sealed_headers_range(
+    /// Verifies that the cumulative gas used by the receipts of each block in `range` doesn't
+    /// decrease, returning the block and transaction numbers of the receipts reporting less gas
+    /// than the previous one in their block.
+    ///
+    /// Empty blocks have nothing to verify. The range is clamped to the blocks of the block body
+    /// indices jar, and verification stops at the first block whose body indices or receipts are
+    /// missing. Returns [`ProviderError::SnapshotOutOfRange`] if `range` falls entirely outside
+    /// it, instead of reporting no violations.
+    ///
+    /// Requires the receipts and block body indices segments, either as this jar or as its
+    /// auxiliars.
+    pub fn verify_cumulative_gas_monotonicity(
         &self,
         range: impl RangeBounds<BlockNumber>,
-    ) -> RethResult<Vec<SealedHeader>> {
-        let range = to_range(range);
+    ) -> RethResult<Vec<(BlockNumber, TxNumber)>> {
+        let bodies_provider = self.segment_provider(SnapshotSegment::BlockBodyIndices)?;
+        let range = bodies_provider.query_range(range)?;
+        let mut bodies = bodies_provider.cursor()?;
+        let mut receipts = self.segment_provider(SnapshotSegment::Receipts)?.cursor()?;
+
+        let mut violations = Vec::new();
+        'blocks: for number in range {
+            let Some(body) =
+                bodies.get_one::<BlockBodyIndicesMask<StoredBlockBodyIndices>>(number.into())?
+            else {
+                break
+            };
+
+            let mut cumulative_gas_used = 0;
+            for tx_num in body.tx_num_range() {
+                let Some(receipt) = receipts.get_one::<ReceiptMask<Receipt>>(tx_num.into())? else {
+                    break 'blocks
+                };
+
+                if receipt.cumulative_gas_used < cumulative_gas_used {
+                    violations.push((number, tx_num));
+                }
+
+                cumulative_gas_used = receipt.cumulative_gas_used;
+            }
+        }
+
+        Ok(violations)
+    }
+
+    /// Verifies the integrity of the jar, returning [`ProviderError::CorruptedSnapshot`] with the
+    /// number of the first row failing it.
+    ///
+    /// `NippyJar` doesn't store checksums, so every row of the covered range is checked for being
+    /// present and stored within the data file according to the offsets index. It's then decoded
+    /// as its segment, with headers also checked against their stored hash and block body indices
+    /// for starting where the previous block ended.
+    pub fn verify(&self) -> RethResult<()> {
+        let segment = self.user_header().segment();
+        let offset = self.user_header().start();
+        let corrupted = |number, reason: String| {
+            RethError::from(ProviderError::CorruptedSnapshot { segment, number, reason })
+        };
+
+        let columns = segment_columns(segment);
+        if self.columns() != columns {
+            return Err(corrupted(
+                offset,
+                format!("expected {columns} columns, found {}", self.columns()),
+            ))
+        }
 
         let mut cursor = self.cursor()?;
-        let mut headers = Vec::with_capacity((range.end - range.start) as usize);
+        let mut next_tx_num = None;
+
+        for number in self.covered_range() {
+            let row = (number - offset) as usize;
+            if row >= self.rows() {
+                return Err(corrupted(number, "missing row".to_string()))
+            }
+            cursor.check_row_bounds(row).map_err(|err| corrupted(number, err.to_string()))?;
+
+            let values = cursor
+                .row_by_number(row)
+                .map_err(|err| corrupted(number, err.to_string()))?
+                .ok_or_else(|| corrupted(number, "missing row".to_string()))?;
 
-        for number in range.start..range.end {
-            match cursor.get_two::<HeaderMask<Header, BlockHash>>(number.into())? {
-                Some((header, hash)) => headers.push(header.seal(hash)),
-                None => return Ok(headers),
+            match segment {
+                SnapshotSegment::Headers => {
+                    let header = decode::<Header>(values[0]);
+                    let hash = decode::<BlockHash>(values[2]);
+                    decode::<CompactU256>(values[1]).map_err(|err| corrupted(number, err))?;
+
+                    let (header, hash) = header
+                        .and_then(|header| Ok((header, hash?)))
+                        .map_err(|err| corrupted(number, err))?;
+                    if header.number != number {
+                        return Err(corrupted(number, format!("header of block #{}", header.number)))
+                    }
+                    if header.hash_slow() != hash {
+                        return Err(corrupted(number, format!("header doesn't match hash {hash}")))
+                    }
+                }
+                SnapshotSegment::Transactions => {
+                    decode::<TransactionSignedNoHash>(values[0])
+                        .map_err(|err| corrupted(number, err))?;
+                }
+                SnapshotSegment::Receipts => {
+                    decode::<Receipt>(values[0]).map_err(|err| corrupted(number, err))?;
+                }
+                SnapshotSegment::BlockBodyIndices => {
+                    let body = decode::<StoredBlockBodyIndices>(values[0])
+                        .map_err(|err| corrupted(number, err))?;
+                    if next_tx_num.is_some_and(|next_tx_num| next_tx_num != body.first_tx_num) {
+                        return Err(corrupted(
+                            number,
+                            format!("first transaction #{} is not consecutive", body.first_tx_num),
+                        ))
+                    }
+                    next_tx_num = Some(body.next_tx_num());
+                }
+                SnapshotSegment::Senders => {
+                    decode::<Address>(values[0]).map_err(|err| corrupted(number, err))?;
+                }
             }
         }
-        Ok(headers)
+
+        Ok(())
     }
 
-    fn sealed_header(&self, number: BlockNumber) -> RethResult<Option<SealedHeader>> {
-        Ok(self
-            .cursor()?
-            .get_two::<HeaderMask<Header, BlockHash>>(number.into())?
-            .map(|(header, hash)| header.seal(hash)))
+    /// Returns an iterator over the rows in `range`, yielding each block or transaction number
+    /// alongside the bytes of the whole row. Meant for debugging the jar contents without knowing
+    /// the segment's schema.
+    ///
+    /// If `decompress` is `true`, the bytes are the decompressed column values, which are the same
+    /// bytes decoded by the typed queries. Otherwise, they're the bytes as stored on disk, before
+    /// any decompression. In both cases, the column values of a row are concatenated in column
+    /// order.
+    ///
+    /// Numbers below the first one in the jar are skipped, and iteration stops at the end of the
+    /// jar.
+    pub fn debug_raw_rows(
+        &self,
+        range: impl RangeBounds<u64>,
+        decompress: bool,
+    ) -> RethResult<impl Iterator<Item = RethResult<(u64, Bytes)>> + '_> {
+        let offset = self.user_header().start();
+        let range = self.present_range(to_range(range));
+        let mut cursor = self.cursor()?;
+
+        Ok(range.map_while(move |number| {
+            let row_number = (number - offset) as usize;
+            let row = if decompress {
+                cursor.row_by_number(row_number)
+            } else {
+                cursor.raw_row_by_number_with_cols(row_number, usize::MAX)
+            };
+
+            match row {
+                Ok(Some(row)) => Some(Ok((number, row.concat().into()))),
+                Ok(None) => None,
+                Err(err) => Some(Err(err.into())),
+            }
+        }))
     }
-}
 
-impl<'a> BlockHashReader for SnapshotJarProvider<'a> {
-    fn block_hash(&self, number: u64) -> RethResult<Option<B256>> {
-        self.cursor()?.get_one::<HeaderMask<BlockHash>>(number.into())
+    /// Returns the number of a block, looking up hashes in the headers segment.
+    fn block_number_of(&self, block: BlockHashOrNumber) -> RethResult<Option<BlockNumber>> {
+        match block {
+            BlockHashOrNumber::Hash(hash) => {
+                self.segment_provider(SnapshotSegment::Headers)?.block_number(hash)
+            }
+            BlockHashOrNumber::Number(number) => Ok(Some(number)),
+        }
     }
 
-    fn canonical_hashes_range(
+    /// Returns whether a block has no transactions, without reading any of them.
+    ///
+    /// Requires the block body indices segment, and the headers one if `block` is a hash. Returns
+    /// `None` if the hash is not found, and [`ProviderError::SnapshotOutOfRange`] if the block is
+    /// outside the block body indices jar.
+    pub fn is_block_empty(&self, block: BlockHashOrNumber) -> RethResult<Option<bool>> {
+        let Some(number) = self.block_number_of(block)? else { return Ok(None) };
+        Ok(self.block_body_indices(number)?.map(|body| body.is_empty()))
+    }
+
+    /// Returns every header in `range`, unlike [`HeaderProvider::headers_range`] which stops at
+    /// the first missing one.
+    ///
+    /// Returns [`ProviderError::HeaderNotFound`] with the first block of `range` missing from the
+    /// jar, so callers relying on a dense chain of headers never get a partial one.
+    pub fn headers_range_exact(
         &self,
-        _start: BlockNumber,
-        _end: BlockNumber,
-    ) -> RethResult<Vec<B256>> {
-        todo!()
+        range: impl RangeBounds<BlockNumber>,
+    ) -> RethResult<Vec<Header>> {
+        let range = to_range(range);
+        if range.is_empty() {
+            return Ok(Vec::new())
+        }
+        self.ensure_covered(&range)?;
+        let present = self.present_range(range.clone());
+        self.metrics().record_range_query(present.end - present.start);
+
+        let mut cursor = self.cursor()?;
+        let mut headers = Vec::with_capacity((present.end - present.start) as usize);
+
+        for num in range {
+            match cursor.get_one::<HeaderMask<Header>>(num.into())? {
+                Some(header) => headers.push(header),
+                None => return Err(ProviderError::HeaderNotFound(num.into()).into()),
+            }
+        }
+
+        Ok(headers)
     }
-}
 
-impl<'a> BlockNumReader for SnapshotJarProvider<'a> {
-    fn chain_info(&self) -> RethResult<ChainInfo> {
-        todo!()
+    /// Returns the headers in `range` in descending order, reading them backwards with a single
+    /// cursor. It's the same as [`HeaderProvider::headers_range`] in reverse.
+    pub fn headers_range_rev(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> RethResult<Vec<Header>> {
+        self.collect_range(self.query_range(range)?.rev(), |cursor, num| {
+            cursor.get_one::<HeaderMask<Header>>(num.into())
+        })
     }
 
-    fn best_block_number(&self) -> RethResult<BlockNumber> {
-        todo!()
+    /// Returns the transactions in `range` in descending order, reading them backwards with a
+    /// single cursor. It's the same as [`TransactionsProvider::transactions_by_tx_range`] in
+    /// reverse.
+    pub fn transactions_by_tx_range_rev(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> RethResult<Vec<TransactionSignedNoHash>> {
+        self.collect_range(self.query_range(range)?.rev(), |cursor, num| {
+            cursor.get_one::<TransactionMask<TransactionSignedNoHash>>(num.into())
+        })
     }
 
-    fn last_block_number(&self) -> RethResult<BlockNumber> {
-        todo!()
+    /// Returns the header of `block_hash` sealed with it, without hashing the header again.
+    ///
+    /// Like [`HeaderProvider::header`], a row whose stored hash differs from `block_hash` is a
+    /// filter false positive, so it returns `None`.
+    pub fn sealed_header_by_hash(
+        &self,
+        block_hash: &BlockHash,
+    ) -> RethResult<Option<SealedHeader>> {
+        self.metrics().record_point_query();
+        Ok(self
+            .cursor()?
+            .get_two::<HeaderMask<Header, BlockHash>>(block_hash.into())?
+            .filter(|(_, hash)| hash == block_hash)
+            .map(|(header, hash)| header.seal(hash)))
     }
 
-    fn block_number(&self, _hash: B256) -> RethResult<Option<BlockNumber>> {
-        todo!()
+    /// Returns the header of each of `hashes` with a single cursor, in the same order. Hashes not
+    /// found in the jar are `None`.
+    ///
+    /// Like [`HeaderProvider::header`], a row whose stored hash differs from the requested one is
+    /// a filter false positive, so it's `None` too.
+    pub fn headers_by_hashes(&self, hashes: &[BlockHash]) -> RethResult<Vec<Option<Header>>> {
+        let mut cursor = self.cursor()?;
+
+        hashes
+            .iter()
+            .map(|block_hash| {
+                self.metrics().record_point_query();
+                Ok(cursor
+                    .get_two::<HeaderMask<Header, BlockHash>>(block_hash.into())?
+                    .filter(|(_, hash)| hash == block_hash)
+                    .map(|(header, _)| header))
+            })
+            .collect()
     }
-}
 
-impl<'a> TransactionsProvider for SnapshotJarProvider<'a> {
-    fn transaction_id(&self, _tx_hash: TxHash) -> RethResult<Option<TxNumber>> {
-        todo!()
+    /// Returns the number of each block of `hashes` with a single cursor, in the same order.
+    ///
+    /// Like [`BlockNumReader::block_number`], hashes not found in the jar and rows whose stored
+    /// hash differs from the requested one are `None`.
+    pub fn block_numbers(&self, hashes: &[B256]) -> RethResult<Vec<Option<BlockNumber>>> {
+        let mut cursor = self.cursor()?;
+
+        hashes
+            .iter()
+            .map(|hash| {
+                self.metrics().record_point_query();
+                Ok(cursor
+                    .get_one::<HeaderMask<BlockHash>>(hash.into())?
+                    .and_then(|res| (res == *hash).then(|| cursor.number() - 1)))
+            })
+            .collect()
     }
 
-    fn transaction_by_id(&self, num: TxNumber) -> RethResult<Option<TransactionSigned>> {
-        TransactionSignedNoHash::decompress(
-            self.cursor()?
-                .row_by_number_with_cols((num - self.user_header().tx_start()) as usize, 0b1)?
-                .ok_or_else(|| ProviderError::TransactionNotFound(num.into()))?[0],
-        )
-        .map(Into::into)
-        .map(Some)
-        .map_err(Into::into)
+    /// Returns the number of each transaction of `hashes` with a single cursor, in the same order.
+    ///
+    /// Like [`TransactionsProvider::transaction_id`], hashes not found in the jar and rows whose
+    /// transaction hash differs from the requested one are `None`.
+    pub fn transaction_ids(&self, hashes: &[TxHash]) -> RethResult<Vec<Option<TxNumber>>> {
+        let mut cursor = self.cursor()?;
+
+        hashes
+            .iter()
+            .map(|hash| {
+                self.metrics().record_point_query();
+                Ok(cursor
+                    .get_one::<TransactionMask<TransactionSignedNoHash>>(hash.into())?
+                    .and_then(|tx| (tx.hash() == *hash).then(|| cursor.number() - 1)))
+            })
+            .collect()
     }
 
-    fn transaction_by_id_no_hash(
+    /// Returns the hash of every `step`-th block of `start..end`, in descending order from
+    /// `end - 1` if `descending`, or else ascending from `start`. Only the sampled blocks are read,
+    /// and those missing from the jar are left out. Nothing is returned if `start >= end`.
+    ///
+    /// Sampled blocks are aligned to `start` or `end - 1` even if the jar covers only part of the
+    /// range, so the same blocks are sampled when querying several jars with it.
+    pub fn canonical_hashes_strided(
         &self,
-        _id: TxNumber,
-    ) -> RethResult<Option<TransactionSignedNoHash>> {
-        todo!()
-    }
+        start: BlockNumber,
+        end: BlockNumber,
+        step: u64,
+        descending: bool,
+    ) -> RethResult<Vec<B256>> {
+        if step == 0 {
+            return Err(ProviderError::ZeroRangeStep.into())
+        }
 
-    fn transaction_by_hash(&self, hash: TxHash) -> RethResult<Option<TransactionSigned>> {
-        // WIP
-        let mut cursor = self.cursor()?;
+        let range = to_range(start..end);
+        self.ensure_covered(&range)?;
+        let present = self.present_range(range.clone());
+        self.metrics().record_range_query(present.end - present.start);
+        if present.is_empty() {
+            return Ok(Vec::new())
+        }
 
-        let tx = TransactionSignedNoHash::decompress(
-            cursor.row_by_key_with_cols(&hash.0, 0b1).unwrap().unwrap()[0],
-        )
-        .unwrap()
-        .with_hash();
+        // Rounds `distance` up to a multiple of `step`
+        let align = |distance: u64| distance.checked_add((step - distance % step) % step);
+        let hash = |cursor: &mut SnapshotCursor<'_>, number: BlockNumber| {
+            cursor.get_one::<HeaderMask<BlockHash>>(number.into())
+        };
 
-        if tx.hash() == hash {
-            return Ok(Some(tx))
+        if descending {
+            let last = range.end - 1;
+            match align(last - (present.end - 1)).and_then(|distance| last.checked_sub(distance)) {
+                Some(first) if first >= present.start => {
+                    self.collect_range((present.start..=first).rev().step_by(step as usize), hash)
+                }
+                _ => Ok(Vec::new()),
+            }
         } else {
-            // check next snapshot
+            match align(present.start - range.start)
+                .and_then(|distance| range.start.checked_add(distance))
+            {
+                Some(first) if first < present.end => {
+                    self.collect_range((first..present.end).step_by(step as usize), hash)
+                }
+                _ => Ok(Vec::new()),
+            }
         }
-        Ok(None)
     }
 
-    fn transaction_by_hash_with_meta(
-        &self,
-        _hash: TxHash,
-    ) -> RethResult<Option<(TransactionSigned, TransactionMeta)>> {
-        todo!()
+    /// Returns the difficulty of a block, as opposed to its total difficulty.
+    pub fn header_difficulty_by_number(&self, num: BlockNumber) -> RethResult<Option<U256>> {
+        Ok(self.header_by_number(num)?.map(|header| header.difficulty))
     }
 
-    fn transaction_block(&self, _id: TxNumber) -> RethResult<Option<BlockNumber>> {
-        todo!()
+    /// Returns the difficulty of each block in `range`, stopping at the first one missing from the
+    /// jar.
+    pub fn headers_difficulty_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> RethResult<Vec<U256>> {
+        self.map_headers_range(range, |_, header| header.difficulty)
     }
 
-    fn transactions_by_block(
+    /// Returns each header in `range` alongside its total difficulty, stopping at the first one
+    /// missing from the jar. Both are read from the same row, so each block is sought once.
+    pub fn headers_td_range(
         &self,
-        _block_id: BlockHashOrNumber,
-    ) -> RethResult<Option<Vec<TransactionSigned>>> {
-        todo!()
+        range: impl RangeBounds<BlockNumber>,
+    ) -> RethResult<Vec<(Header, U256)>> {
+        self.collect_range(self.query_range(range)?, |cursor, num| {
+            Ok(cursor
+                .get_two::<HeaderMask<Header, CompactU256>>(num.into())?
+                .map(|(header, td)| (header, td.into())))
+        })
     }
 
-    fn transactions_by_block_range(
+    /// Applies `f` to each header in `range` as it's read, returning only the projected values.
+    /// Stops at the first header missing from the jar.
+    ///
+    /// Headers are still decoded, but are dropped right after `f`, so only what it keeps is held in
+    /// memory.
+    pub fn map_headers_range<T>(
         &self,
-        _range: impl RangeBounds<BlockNumber>,
-    ) -> RethResult<Vec<Vec<TransactionSigned>>> {
-        todo!()
+        range: impl RangeBounds<BlockNumber>,
+        mut f: impl FnMut(BlockNumber, &Header) -> T,
+    ) -> RethResult<Vec<T>> {
+        self.collect_range(self.query_range(range)?, |cursor, num| {
+            Ok(cursor.get_one::<HeaderMask<Header>>(num.into())?.map(|header| f(num, &header)))
+        })
     }
 
-    fn senders_by_tx_range(&self, _range: impl RangeBounds<TxNumber>) -> RethResult<Vec<Address>> {
-        todo!()
+    /// Returns the headers in `range` like [`HeaderProvider::headers_range`], decoding them in
+    /// parallel for wide ranges.
+    ///
+    /// The range is split into a chunk per thread of the global rayon pool, each read with its own
+    /// cursor, and their headers are concatenated in order. Ranges too narrow to benefit from it
+    /// are read sequentially instead.
+    pub fn headers_range_par(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> RethResult<Vec<Header>> {
+        let range = self.query_range(range)?;
+        let len = range.end - range.start;
+        if len < HEADERS_PAR_MIN_RANGE {
+            return self.collect_range(range, |cursor, num| {
+                cursor.get_one::<HeaderMask<Header>>(num.into())
+            })
+        }
+
+        let threads = rayon::current_num_threads() as u64;
+        self.headers_range_in_chunks(range, (len + threads - 1) / threads)
     }
 
-    fn transactions_by_tx_range(
+    /// Reads the headers of `range` in parallel, in chunks of `chunk_size` blocks.
+    ///
+    /// Like the sequential read, the result ends at the first header missing from the jar: a chunk
+    /// returning fewer headers than it spans is the last one kept, and errors of chunks after it
+    /// are ignored.
+    fn headers_range_in_chunks(
         &self,
-        _range: impl RangeBounds<TxNumber>,
-    ) -> RethResult<Vec<reth_primitives::TransactionSignedNoHash>> {
-        todo!()
+        range: Range<BlockNumber>,
+        chunk_size: u64,
+    ) -> RethResult<Vec<Header>> {
+        let chunks = range
+            .clone()
+            .step_by(chunk_size as usize)
+            .map(|start| start..(start + chunk_size).min(range.end))
+            .collect::<Vec<_>>();
+
+        let chunks = chunks
+            .into_par_iter()
+            .map(|chunk| {
+                let len = (chunk.end - chunk.start) as usize;
+                let headers = self.collect_range(chunk, |cursor, num| {
+                    cursor.get_one::<HeaderMask<Header>>(num.into())
+                })?;
+                Ok((headers.len() == len, headers))
+            })
+            .collect::<Vec<RethResult<_>>>();
+
+        let mut headers = Vec::with_capacity((range.end - range.start) as usize);
+        for chunk in chunks {
+            let (complete, chunk) = chunk?;
+            headers.extend(chunk);
+            if !complete {
+                break
+            }
+        }
+        Ok(headers)
     }
 
-    fn transaction_sender(&self, _id: TxNumber) -> RethResult<Option<Address>> {
-        todo!()
+    /// Returns an iterator over the headers in `range`, which reads and decodes them one at a time
+    /// with a single cursor. It ends at the first header missing from the jar.
+    pub fn headers_iter(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> RethResult<impl Iterator<Item = RethResult<Header>> + '_> {
+        let range = self.query_range(range)?;
+        let mut cursor = self.cursor()?;
+
+        Ok(range.map_while(move |num| cursor.get_one::<HeaderMask<Header>>(num.into()).transpose()))
     }
-}
 
+    /// Returns an iterator over the sealed headers in `range`, which reads and decodes them one at
+    /// a time with a single cursor. It ends at the first header missing from the jar.
+    pub fn sealed_headers_iter(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> RethResult<impl Iterator<Item = RethResult<SealedHeader>> + '_> {
+        let range = self.query_range(range)?;
+        let mut cursor = self.cursor()?;
+
+        Ok(range.map_while(move |num| {
+            cursor
+                .get_two::<HeaderMask<Header, BlockHash>>(num.into())
+                .map(|header| header.map(|(header, hash)| header.seal(hash)))
+                .transpose()
+        }))
+    }
+
+    /// Returns an iterator over the transactions in `range`, which reads and decodes them one at a
+    /// time with a single cursor. It ends at the first transaction missing from the jar.
+    pub fn transactions_iter(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> RethResult<impl Iterator<Item = RethResult<TransactionSignedNoHash>> + '_> {
+        let range = self.query_range(range)?;
+        let mut cursor = self.cursor()?;
+
+        Ok(range.map_while(move |num| {
+            cursor.get_one::<TransactionMask<TransactionSignedNoHash>>(num.into()).transpose()
+        }))
+    }
+
+    /// Returns an iterator over the transactions from `start` to the end of the jar, alongside
+    /// their numbers, which reads them one at a time with a single cursor. Meant for resuming a
+    /// scan from the number following the last one processed.
+    ///
+    /// It starts at the first transaction of the jar if `start` is before it, and ends at
+    /// [`Self::tx_range`]'s end or at the first transaction missing from the jar, whichever comes
+    /// first. Unlike range queries, it's empty instead of returning
+    /// [`ProviderError::SnapshotOutOfRange`] if `start` is beyond the last transaction: resuming
+    /// right after the last one of the jar is expected once it's been fully scanned.
+    pub fn scan_from(
+        &self,
+        start: TxNumber,
+    ) -> RethResult<impl Iterator<Item = RethResult<(TxNumber, TransactionSignedNoHash)>> + '_>
+    {
+        let end = self.tx_range().end;
+        let mut cursor = self.cursor()?;
+        cursor.seek(start);
+
+        Ok(std::iter::from_fn(move || {
+            if cursor.number() >= end {
+                return None
+            }
+            cursor.next_one::<TransactionMask<TransactionSignedNoHash>>().transpose()
+        }))
+    }
+
+    /// Returns an iterator over the transactions of each block in `range` alongside its number,
+    /// which reads one block at a time with a single cursor per segment. Blocks without
+    /// transactions yield an empty `Vec`.
+    ///
+    /// Requires the block body indices and transactions segments, and returns
+    /// [`ProviderError::SnapshotOutOfRange`] if `range` is outside the block body indices jar.
+    ///
+    /// Each block is read as in [`TransactionsProvider::transactions_by_block`]. It ends at the
+    /// first block that isn't found there: one missing from the block body indices jar, or whose
+    /// transactions are all missing from the transactions jar. Its errors are yielded as well, for
+    /// transactions outside the transactions jar or only partially present in it.
+    pub fn block_bodies_iter(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> RethResult<impl Iterator<Item = RethResult<(BlockNumber, Vec<TransactionSigned>)>> + '_>
+    {
+        let bodies_provider = self.segment_provider(SnapshotSegment::BlockBodyIndices)?;
+        let range = bodies_provider.query_range(range)?;
+        let mut bodies = bodies_provider.cursor()?;
+        let transactions_provider = self.segment_provider(SnapshotSegment::Transactions)?;
+        let mut cursor = transactions_provider.cursor()?;
+
+        Ok(range.map_while(move |number| {
+            let body = match bodies
+                .get_one::<BlockBodyIndicesMask<StoredBlockBodyIndices>>(number.into())
+            {
+                Ok(Some(body)) => body,
+                Ok(None) => return None,
+                Err(err) => return Some(Err(err)),
+            };
+
+            if let Err(err) = transactions_provider.ensure_covered(&body.tx_num_range()) {
+                return Some(Err(err))
+            }
+
+            let mut transactions = Vec::with_capacity(body.tx_count() as usize);
+            for num in body.tx_num_range() {
+                match cursor.get_one::<TransactionMask<TransactionSignedNoHash>>(num.into()) {
+                    Ok(Some(tx)) => transactions.push(tx.with_hash()),
+                    Ok(None) if transactions.is_empty() => return None,
+                    Ok(None) => return Some(Err(ProviderError::BlockBodyTransactionCount.into())),
+                    Err(err) => return Some(Err(err)),
+                }
+            }
+            Some(Ok((number, transactions)))
+        }))
+    }
+
+    /// Returns the transactions in `range` matching `pred`, stopping at the first transaction
+    /// missing from the jar.
+    ///
+    /// It's applied as each transaction is read, so only the matching ones are kept in memory.
+    pub fn transactions_by_tx_range_filter(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+        pred: impl Fn(&TransactionSignedNoHash) -> bool,
+    ) -> RethResult<Vec<TransactionSignedNoHash>> {
+        self.transactions_iter(range)?.filter(|tx| tx.as_ref().map_or(true, &pred)).collect()
+    }
+
+    /// Returns the hash of each transaction in `range` alongside its number, stopping at the first
+    /// transaction missing from the jar like [`TransactionsProvider::transactions_by_tx_range`].
+    ///
+    /// Transactions are read one at a time with a single cursor, and dropped as soon as their hash
+    /// is computed.
+    pub fn transaction_hashes_by_tx_range(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> RethResult<Vec<(TxNumber, TxHash)>> {
+        self.collect_range(self.query_range(range)?, |cursor, num| {
+            Ok(cursor
+                .get_one::<TransactionMask<TransactionSignedNoHash>>(num.into())?
+                .map(|tx| (num, tx.hash())))
+        })
+    }
+
+    /// Returns up to `count` transactions alongside their numbers, starting at `start` or at the
+    /// first transaction of the jar if it's later. Fewer are returned if the jar ends before, and
+    /// [`ProviderError::SnapshotOutOfRange`] if it ends before `start`.
+    pub fn transactions_from(
+        &self,
+        start: TxNumber,
+        count: usize,
+    ) -> RethResult<Vec<(TxNumber, TransactionSignedNoHash)>> {
+        let start = start.max(self.user_header().start());
+        let range = self.query_range(start..start.saturating_add(count as u64))?;
+        self.collect_range(range, |cursor, num| {
+            Ok(cursor
+                .get_one::<TransactionMask<TransactionSignedNoHash>>(num.into())?
+                .map(|tx| (num, tx)))
+        })
+    }
+
+    /// Returns the transaction `num` as stored in the jar, decompressed but not decoded. Meant for
+    /// forwarding it without a decoding round-trip.
+    ///
+    /// The bytes are the `Compact` encoding of [`TransactionSignedNoHash`] used by the database,
+    /// **not** its canonical RLP encoding. [`TransactionSignedNoHash::decompress`] decodes them.
+    pub fn raw_transaction_by_id(&self, num: TxNumber) -> RethResult<Option<Bytes>> {
+        self.raw_value_by_number::<TransactionMask<TransactionSignedNoHash>>(num)
+    }
+
+    /// Returns the receipt of the transaction `num` as stored in the jar, decompressed but not
+    /// decoded.
+    ///
+    /// The bytes are the `Compact` encoding of [`Receipt`] used by the database, **not** its
+    /// canonical RLP encoding. [`Receipt::decompress`] decodes them.
+    pub fn raw_receipt_by_id(&self, num: TxNumber) -> RethResult<Option<Bytes>> {
+        self.raw_value_by_number::<ReceiptMask<Receipt>>(num)
+    }
+
+    /// Returns the decompressed bytes of the column selected by `M` on the row of `num`.
+    fn raw_value_by_number<M: ColumnSelectorOne>(&self, num: u64) -> RethResult<Option<Bytes>> {
+        self.ensure_covered_number(num)?;
+        Ok(self.cursor()?.get(num.into(), M::MASK)?.map(|row| row.concat().into()))
+    }
+
+    /// Returns the number of bytes the transaction `num` takes in the data file, as stored after
+    /// compression. Read from the offsets index, so nothing is decoded.
+    ///
+    /// Requires the transactions segment, either as this jar or as one of its auxiliars.
+    pub fn transaction_stored_size_by_id(&self, num: TxNumber) -> RethResult<Option<u64>> {
+        let transactions = self.segment_provider(SnapshotSegment::Transactions)?;
+        transactions.ensure_covered_number(num)?;
+        transactions.transaction_stored_size(&mut transactions.cursor()?, num)
+    }
+
+    /// Returns the number of bytes each transaction in `range` takes in the data file, stopping at
+    /// the first one missing from the jar.
+    ///
+    /// Requires the transactions segment, either as this jar or as one of its auxiliars.
+    pub fn transactions_stored_size_by_tx_range(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> RethResult<Vec<u64>> {
+        let transactions = self.segment_provider(SnapshotSegment::Transactions)?;
+        transactions.collect_range(transactions.query_range(range)?, |cursor, num| {
+            transactions.transaction_stored_size(cursor, num)
+        })
+    }
+
+    /// Returns the number of bytes the transaction `num` of this transactions jar takes in the
+    /// data file, which must be within its range.
+    fn transaction_stored_size(
+        &self,
+        cursor: &mut SnapshotCursor<'_>,
+        num: TxNumber,
+    ) -> RethResult<Option<u64>> {
+        let row = num - self.user_header().start();
+        Ok(cursor
+            .raw_row_by_number_with_cols(
+                row as usize,
+                TransactionMask::<TransactionSignedNoHash>::MASK,
+            )?
+            .map(|row| row.iter().map(|value| value.len() as u64).sum()))
+    }
+
+    /// Returns the blob versioned hashes of the transaction `num`, which are empty unless it's an
+    /// EIP-4844 transaction.
+    pub fn transaction_blob_hashes_by_id(&self, num: TxNumber) -> RethResult<Option<Vec<B256>>> {
+        Ok(self
+            .transaction_by_id_no_hash(num)?
+            .map(|tx| tx.blob_versioned_hashes().unwrap_or_default()))
+    }
+
+    /// Returns the blob versioned hashes of each transaction in `range`, stopping at the first one
+    /// missing from the jar.
+    pub fn transactions_blob_hashes_by_tx_range(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> RethResult<Vec<Vec<B256>>> {
+        Ok(self
+            .transactions_by_tx_range(range)?
+            .into_iter()
+            .map(|tx| tx.blob_versioned_hashes().unwrap_or_default())
+            .collect())
+    }
+
+    /// Appends the receipts of the transactions in `range` to `out`, stopping at the first one
+    /// missing from the jar.
+    ///
+    /// Any previous content of `out` is kept, so the same buffer can be reused across ranges.
+    pub fn receipts_by_tx_range_into(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+        out: &mut Vec<Receipt>,
+    ) -> RethResult<()> {
+        // The range is read directly instead of through `receipts_iter`, which would check and
+        // record it a second time
+        let range = self.query_range(range)?;
+        if range.is_empty() {
+            return Ok(())
+        }
+
+        let mut cursor = self.cursor()?;
+        out.reserve((range.end - range.start) as usize);
+
+        for num in range {
+            match cursor.get_one::<ReceiptMask<Receipt>>(num.into())? {
+                Some(receipt) => out.push(receipt),
+                None => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns an iterator over the receipts in `range`, which reads and decodes them one at a time
+    /// with a single cursor. It ends at the first receipt missing from the jar.
+    pub fn receipts_iter(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> RethResult<impl Iterator<Item = RethResult<Receipt>> + '_> {
+        let range = self.query_range(range)?;
+        let mut cursor = self.cursor()?;
+
+        Ok(range
+            .map_while(move |num| cursor.get_one::<ReceiptMask<Receipt>>(num.into()).transpose()))
+    }
+
+    /// Returns the receipts of the transactions in `range` matching `pred`, stopping at the first
+    /// one missing from the jar.
+    ///
+    /// It's applied as each receipt is read, so only the matching ones are kept in memory.
+    pub fn receipts_by_tx_range_filter(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+        pred: impl Fn(&Receipt) -> bool,
+    ) -> RethResult<Vec<Receipt>> {
+        self.receipts_iter(range)?.filter(|receipt| receipt.as_ref().map_or(true, &pred)).collect()
+    }
+
+    /// Returns the receipts of the transactions in `range`, stopping at the first one missing from
+    /// the jar.
+    pub fn receipts_by_tx_range(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> RethResult<Vec<Receipt>> {
+        self.collect_range(self.query_range(range)?, |cursor, num| {
+            cursor.get_one::<ReceiptMask<Receipt>>(num.into())
+        })
+    }
+
+    /// Returns the transactions in `range` alongside their receipts, reading both segments in
+    /// lockstep with a cursor each.
+    ///
+    /// Requires the transactions and receipts segments, either of which can be an auxiliar. Only
+    /// numbers covered by both jars are returned, so the rest of `range` is skipped when one of
+    /// them ends before the other. Within them, it stops where both jars end, while a number
+    /// present in one jar but missing from the other returns
+    /// [`ProviderError::SnapshotSegmentsMisaligned`].
+    ///
+    /// Returns [`ProviderError::SnapshotOutOfRange`] if `range` falls entirely outside either jar.
+    pub fn tx_and_receipt_by_tx_range(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> RethResult<Vec<(TransactionSignedNoHash, Receipt)>> {
+        let range = to_range(range);
+        let transactions = self.segment_provider(SnapshotSegment::Transactions)?;
+        let receipts = self.segment_provider(SnapshotSegment::Receipts)?;
+        transactions.ensure_covered(&range)?;
+        receipts.ensure_covered(&range)?;
+
+        let (transactions_covered, receipts_covered) =
+            (transactions.covered_range(), receipts.covered_range());
+        let start = range.start.max(*transactions_covered.start()).max(*receipts_covered.start());
+        let end = range
+            .end
+            .min(transactions_covered.end().saturating_add(1))
+            .min(receipts_covered.end().saturating_add(1))
+            .max(start);
+        if start == end {
+            return Ok(Vec::new())
+        }
+        transactions.metrics().record_range_query(end - start);
+        receipts.metrics().record_range_query(end - start);
+
+        let misaligned = |number, present, missing| {
+            Err(ProviderError::SnapshotSegmentsMisaligned { number, present, missing }.into())
+        };
+        let mut transactions_cursor = transactions.cursor()?;
+        let mut receipts_cursor = receipts.cursor()?;
+        let mut pairs = Vec::with_capacity(((end - start) as usize).min(transactions.rows()));
+        for num in start..end {
+            match (
+                transactions_cursor
+                    .get_one::<TransactionMask<TransactionSignedNoHash>>(num.into())?,
+                receipts_cursor.get_one::<ReceiptMask<Receipt>>(num.into())?,
+            ) {
+                (Some(transaction), Some(receipt)) => pairs.push((transaction, receipt)),
+                (None, None) => break,
+                (Some(_), None) => {
+                    return misaligned(num, SnapshotSegment::Transactions, SnapshotSegment::Receipts)
+                }
+                (None, Some(_)) => {
+                    return misaligned(num, SnapshotSegment::Receipts, SnapshotSegment::Transactions)
+                }
+            }
+        }
+        Ok(pairs)
+    }
+
+    /// Returns the receipts in `range` with at least one log emitted by `address`, alongside their
+    /// transaction numbers. Stops at the first receipt missing from the jar.
+    ///
+    /// If the headers and block body indices segments are available, as this jar or as its
+    /// auxiliars, the logs bloom of each block is checked first and the receipts of blocks that
+    /// can't hold logs of `address` are skipped without being decoded. Blooms can have false
+    /// positives, so this is only an optimization: the logs of every remaining receipt are scanned
+    /// to confirm the match.
+    pub fn receipts_with_log_address_by_tx_range(
+        &self,
+        address: Address,
+        range: impl RangeBounds<TxNumber>,
+    ) -> RethResult<Vec<(TxNumber, Receipt)>> {
+        self.receipts_with_log_matching(
+            range,
+            |bloom| bloom.contains_input(BloomInput::Raw(address.as_slice())),
+            |log| log.address == address,
+        )
+    }
+
+    /// Returns the receipts in `range` with at least one log matching the filter of `addresses`
+    /// and `topics`, alongside their transaction numbers. Stops at the first receipt missing from
+    /// the jar.
+    ///
+    /// Follows the `eth_getLogs` filter semantics: a log matches if it was emitted by any of
+    /// `addresses`, and for each position of `topics`, its topic at that position is any of the
+    /// given ones. Empty `addresses` or an empty position match anything.
+    ///
+    /// Receipts don't store their logs bloom, so the one of each block is checked first instead,
+    /// as in [`Self::receipts_with_log_address_by_tx_range`]. It's only a prefilter: every log of
+    /// the remaining receipts is still matched exactly.
+    pub fn receipts_matching_range(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+        addresses: &[Address],
+        topics: &[Vec<B256>],
+    ) -> RethResult<Vec<(TxNumber, Receipt)>> {
+        self.receipts_with_log_matching(
+            range,
+            |bloom| {
+                let contains = |value: &[u8]| bloom.contains_input(BloomInput::Raw(value));
+                (addresses.is_empty() ||
+                    addresses.iter().any(|address| contains(address.as_slice()))) &&
+                    topics.iter().all(|position| {
+                        position.is_empty() ||
+                            position.iter().any(|topic| contains(topic.as_slice()))
+                    })
+            },
+            |log| {
+                (addresses.is_empty() || addresses.contains(&log.address)) &&
+                    topics.iter().enumerate().all(|(index, position)| {
+                        position.is_empty() ||
+                            log.topics.get(index).map_or(false, |topic| position.contains(topic))
+                    })
+            },
+        )
+    }
+
+    /// Returns the receipts in `range` with at least one log satisfying `matches`, alongside their
+    /// transaction numbers. Stops at the first receipt missing from the jar.
+    ///
+    /// If the headers and block body indices segments are available, the receipts of blocks whose
+    /// logs bloom doesn't satisfy `may_match` are skipped without being decoded.
+    fn receipts_with_log_matching(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+        may_match: impl Fn(&Bloom) -> bool,
+        matches: impl Fn(&Log) -> bool,
+    ) -> RethResult<Vec<(TxNumber, Receipt)>> {
+        let receipts = self.segment_provider(SnapshotSegment::Receipts)?;
+        let range = receipts.query_range(range)?;
+        let mut cursor = receipts.cursor()?;
+
+        // The block holding the start of the range is searched once, and the following ones are
+        // then walked forward alongside the receipts with a cursor per segment.
+        let mut blooms = None;
+        if let (Ok(headers), Ok(bodies)) = (
+            self.segment_provider(SnapshotSegment::Headers),
+            self.segment_provider(SnapshotSegment::BlockBodyIndices),
+        ) {
+            let mut bodies_cursor = bodies.cursor()?;
+            if let Some((block, _)) =
+                bodies.search_block_body_indices(&mut bodies_cursor, range.start)?
+            {
+                blooms = Some((headers, headers.cursor()?, bodies_cursor, block));
+            }
+        }
+        // First transaction after the block whose bloom was last checked
+        let mut block_end = range.start;
+
+        let mut receipts = Vec::new();
+        let mut num = range.start;
+        while num < range.end {
+            if let Some((headers, headers_cursor, bodies_cursor, block)) =
+                blooms.as_mut().filter(|_| num >= block_end)
+            {
+                // Skips the blocks without transactions
+                let body = loop {
+                    match bodies_cursor
+                        .get_one::<BlockBodyIndicesMask<StoredBlockBodyIndices>>((*block).into())?
+                    {
+                        Some(body) if body.next_tx_num() <= num => *block += 1,
+                        body => break body,
+                    }
+                };
+                let Some(body) = body else {
+                    // Outside of the block body indices jar, so blooms can't be checked.
+                    blooms = None;
+                    continue
+                };
+
+                block_end = body.next_tx_num();
+                let may_contain = !headers.contains_block(*block) ||
+                    headers_cursor
+                        .get_one::<HeaderMask<Header>>((*block).into())?
+                        .map_or(true, |header| may_match(&header.logs_bloom));
+                *block += 1;
+                if !may_contain {
+                    num = block_end;
+                    continue
+                }
+            }
+
+            let Some(receipt) = cursor.get_one::<ReceiptMask<Receipt>>(num.into())? else { break };
+            if receipt.logs.iter().any(&matches) {
+                receipts.push((num, receipt));
+            }
+
+            num += 1;
+        }
+
+        Ok(receipts)
+    }
+
+    /// Returns the transaction with the given hash alongside its number.
+    ///
+    /// The hash is looked up directly through the filter and perfect hashing function of the jar,
+    /// so a single row is read at most. Since the latter maps unknown hashes to some row too, the
+    /// hash of the read transaction has to match.
+    fn transaction_with_id_by_hash(
+        &self,
+        hash: TxHash,
+    ) -> RethResult<Option<(TxNumber, TransactionSignedNoHash)>> {
+        self.metrics().record_point_query();
+        let mut cursor = self.cursor()?;
+
+        Ok(cursor
+            .get_one::<TransactionMask<TransactionSignedNoHash>>((&hash).into())?
+            .and_then(|tx| (tx.hash() == hash).then(|| (cursor.number() - 1, tx))))
+    }
+
+    /// Returns the number and [`StoredBlockBodyIndices`] of the block holding the transaction
+    /// `num`, which requires the block body indices segment.
+    ///
+    /// Binary searches over the block bodies of the segment, since they're sorted by their first
+    /// transaction number.
+    fn block_body_indices_by_tx(
+        &self,
+        num: TxNumber,
+    ) -> RethResult<Option<(BlockNumber, StoredBlockBodyIndices)>> {
+        let provider = self.segment_provider(SnapshotSegment::BlockBodyIndices)?;
+        provider.search_block_body_indices(&mut provider.cursor()?, num)
+    }
+
+    /// Binary searches with `cursor` for the block of this block body indices jar holding the
+    /// transaction `num`, as in [`Self::block_body_indices_by_tx`].
+    fn search_block_body_indices(
+        &self,
+        cursor: &mut SnapshotCursor<'_>,
+        num: TxNumber,
+    ) -> RethResult<Option<(BlockNumber, StoredBlockBodyIndices)>> {
+        let mut blocks = self.block_range();
+
+        while !blocks.is_empty() {
+            let mid = blocks.start + (blocks.end - blocks.start) / 2;
+            let Some(body) =
+                cursor.get_one::<BlockBodyIndicesMask<StoredBlockBodyIndices>>(mid.into())?
+            else {
+                // The jar holds fewer blocks than its range
+                blocks.end = mid;
+                continue
+            };
+
+            if num < body.first_tx_num {
+                blocks.end = mid;
+            } else if num >= body.next_tx_num() {
+                blocks.start = mid + 1;
+            } else {
+                return Ok(Some((mid, body)))
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Returns the transaction `num` along with its 0-based index within its block.
+    ///
+    /// Requires the transactions and block body indices segments, either as this jar or as its
+    /// auxiliars.
+    pub fn transaction_with_block_index_by_id(
+        &self,
+        num: TxNumber,
+    ) -> RethResult<Option<(TransactionSigned, u64)>> {
+        let Some((_, body)) = self.block_body_indices_by_tx(num)? else { return Ok(None) };
+        let Some(transaction) =
+            self.segment_provider(SnapshotSegment::Transactions)?.transaction_by_id_no_hash(num)?
+        else {
+            return Ok(None)
+        };
+
+        Ok(Some((transaction.with_hash(), num - body.first_tx_num)))
+    }
+
+    /// Returns the [`TransactionMeta`] of the transaction `num`, with the fields the attached
+    /// segments can provide, without requiring the header of its block.
+    ///
+    /// Requires the transactions and block body indices segments, either as this jar or as its
+    /// auxiliars, which provide `tx_hash`, `index` and `block_number`. If the headers segment is
+    /// attached too and holds the block, `block_hash`, `base_fee` and `excess_blob_gas` are read
+    /// from its header. Otherwise, `block_hash` is zero and the others are `None`.
+    pub fn transaction_meta_by_id(&self, num: TxNumber) -> RethResult<Option<TransactionMeta>> {
+        let Some((block_number, body)) = self.block_body_indices_by_tx(num)? else {
+            return Ok(None)
+        };
+        let Some(transaction) =
+            self.segment_provider(SnapshotSegment::Transactions)?.transaction_by_id_no_hash(num)?
+        else {
+            return Ok(None)
+        };
+
+        let mut meta = TransactionMeta {
+            tx_hash: transaction.hash(),
+            index: num - body.first_tx_num,
+            block_number,
+            ..Default::default()
+        };
+
+        if let Ok(headers) = self.segment_provider(SnapshotSegment::Headers) {
+            if let Some(header) = headers.sealed_header(block_number)? {
+                meta.block_hash = header.hash();
+                meta.base_fee = header.base_fee_per_gas;
+                meta.excess_blob_gas = header.excess_blob_gas;
+            }
+        }
+
+        Ok(Some(meta))
+    }
+
+    /// Returns the whole [`SealedBlock`], with its header, transactions, ommers and withdrawals.
+    ///
+    /// Requires the headers, transactions and block body indices segments, either as this jar or
+    /// as its auxiliars. Returns [`ProviderError::MissingSnapshotSegment`] naming the first one
+    /// missing, and [`ProviderError::SnapshotOutOfRange`] if the block is not covered by them.
+    ///
+    /// Ommers and withdrawals are not snapshotted, so they can only be rebuilt when the header
+    /// roots show they're empty. Blocks with any of them are not supported.
+    pub fn sealed_block(&self, block: BlockHashOrNumber) -> RethResult<Option<SealedBlock>> {
+        let segment_provider = |segment| {
+            self.segment_provider(segment)
+                .map_err(|_| ProviderError::MissingSnapshotSegment(segment))
+        };
+        let headers = segment_provider(SnapshotSegment::Headers)?;
+        let transactions = segment_provider(SnapshotSegment::Transactions)?;
+        segment_provider(SnapshotSegment::BlockBodyIndices)?;
+
+        let Some(number) = headers.convert_hash_or_number(block)? else { return Ok(None) };
+        let Some(header) = headers.sealed_header(number)? else { return Ok(None) };
+        let Some(body) = self.block_body_indices(number)? else { return Ok(None) };
+
+        let ommers = if header.ommers_hash == EMPTY_OMMER_ROOT_HASH {
+            Vec::new()
+        } else {
+            return Err(ProviderError::UnsupportedProvider.into())
+        };
+
+        let withdrawals = match header.withdrawals_root {
+            None => None,
+            Some(root) if root == EMPTY_WITHDRAWALS => Some(Vec::new()),
+            Some(_) => return Err(ProviderError::UnsupportedProvider.into()),
+        };
+
+        let transactions = transactions.transactions_by_tx_range(body.tx_num_range())?;
+        if transactions.len() as u64 != body.tx_count() {
+            return Err(ProviderError::BlockBodyTransactionCount.into())
+        }
+
+        Ok(Some(SealedBlock {
+            header,
+            body: transactions.into_iter().map(|tx| tx.with_hash()).collect(),
+            ommers,
+            withdrawals,
+        }))
+    }
+
+    /// Returns the total fees paid by the transactions of a block: the sum of the gas used by each
+    /// of them times its effective gas price.
+    ///
+    /// The effective gas price includes the base fee, so the total covers both the fees burned
+    /// since London and the tips paid to the block producer. Fees paid for blob gas are excluded.
+    ///
+    /// Requires the headers, transactions, receipts and block body indices segments, either as
+    /// this jar or as its auxiliars. Returns `None` if `block` is a hash not found in the headers
+    /// jar, and [`ProviderError::SnapshotOutOfRange`] if the block is outside the headers or block
+    /// body indices jars.
+    pub fn block_total_fees(&self, block: BlockHashOrNumber) -> RethResult<Option<U256>> {
+        let headers = self.segment_provider(SnapshotSegment::Headers)?;
+        let Some(number) = headers.convert_hash_or_number(block)? else { return Ok(None) };
+        let Some(header) = headers.header_by_number(number)? else { return Ok(None) };
+        let Some(body) = self.block_body_indices(number)? else { return Ok(None) };
+
+        let transactions = self
+            .segment_provider(SnapshotSegment::Transactions)?
+            .transactions_by_tx_range(body.tx_num_range())?;
+        if transactions.len() as u64 != body.tx_count() {
+            return Err(ProviderError::BlockBodyTransactionCount.into())
+        }
+
+        let receipts = self
+            .segment_provider(SnapshotSegment::Receipts)?
+            .receipts_by_tx_range(body.tx_num_range())?;
+        if receipts.len() as u64 != body.tx_count() {
+            return Err(ProviderError::ReceiptNotFound(
+                (body.first_tx_num + receipts.len() as u64).into(),
+            )
+            .into())
+        }
+
+        let mut cumulative_gas_used = 0;
+        let mut fees = U256::ZERO;
+        for (tx, receipt) in transactions.iter().zip(receipts) {
+            // Receipts only hold the gas used by the block up to and including their transaction.
+            let gas_used = receipt.cumulative_gas_used.saturating_sub(cumulative_gas_used);
+            cumulative_gas_used = receipt.cumulative_gas_used;
+
+            fees +=
+                U256::from(gas_used) * U256::from(tx.effective_gas_price(header.base_fee_per_gas));
+        }
+
+        Ok(Some(fees))
+    }
+
+    /// Returns the effective tip per gas paid to the block producer by each transaction of a block,
+    /// in block order.
+    ///
+    /// The tip is computed against the base fee of the block as:
+    /// * Legacy and EIP-2930 transactions: `gas_price - base_fee`.
+    /// * EIP-1559 and EIP-4844 transactions: `min(max_priority_fee_per_gas, max_fee_per_gas -
+    ///   base_fee)`.
+    ///
+    /// Blocks prior to London have no base fee, so the whole gas price is the tip. A transaction
+    /// whose fee cap is below the base fee, which can't be part of a valid block, has a zero tip.
+    ///
+    /// Requires the headers, transactions and block body indices segments, either as this jar or
+    /// as its auxiliars. Returns `None` if `block` is a hash not found in the headers jar, and
+    /// [`ProviderError::SnapshotOutOfRange`] if the block is outside the headers or block body
+    /// indices jars.
+    pub fn effective_tips_by_block(
+        &self,
+        block: BlockHashOrNumber,
+    ) -> RethResult<Option<Vec<u128>>> {
+        let headers = self.segment_provider(SnapshotSegment::Headers)?;
+        let Some(number) = headers.convert_hash_or_number(block)? else { return Ok(None) };
+        let Some(header) = headers.header_by_number(number)? else { return Ok(None) };
+        let Some(body) = self.block_body_indices(number)? else { return Ok(None) };
+
+        let transactions = self
+            .segment_provider(SnapshotSegment::Transactions)?
+            .transactions_by_tx_range(body.tx_num_range())?;
+        if transactions.len() as u64 != body.tx_count() {
+            return Err(ProviderError::BlockBodyTransactionCount.into())
+        }
+
+        Ok(Some(
+            transactions
+                .iter()
+                .map(|tx| tx.effective_gas_tip(header.base_fee_per_gas).unwrap_or_default())
+                .collect(),
+        ))
+    }
+}
+
+impl<'a> HeaderProvider for SnapshotJarProvider<'a> {
+    fn header(&self, block_hash: &BlockHash) -> RethResult<Option<Header>> {
+        self.metrics().record_point_query();
+        Ok(self
+            .cursor()?
+            .get_two::<HeaderMask<Header, BlockHash>>(block_hash.into())?
+            .filter(|(_, hash)| hash == block_hash)
+            .map(|(header, _)| header))
+    }
+
+    fn header_by_number(&self, num: BlockNumber) -> RethResult<Option<Header>> {
+        self.ensure_covered_number(num)?;
+        self.cursor()?.get_one::<HeaderMask<Header>>(num.into())
+    }
+
+    fn header_td(&self, block_hash: &BlockHash) -> RethResult<Option<U256>> {
+        self.metrics().record_point_query();
+        Ok(self
+            .cursor()?
+            .get_two::<HeaderMask<CompactU256, BlockHash>>(block_hash.into())?
+            .filter(|(_, hash)| hash == block_hash)
+            .map(|(td, _)| td.into()))
+    }
+
+    fn header_td_by_number(&self, num: BlockNumber) -> RethResult<Option<U256>> {
+        self.ensure_covered_number(num)?;
+        Ok(self.cursor()?.get_one::<HeaderMask<CompactU256>>(num.into())?.map(Into::into))
+    }
+
+    fn headers_range(&self, range: impl RangeBounds<BlockNumber>) -> RethResult<Vec<Header>> {
+        self.collect_range(self.query_range(range)?, |cursor, num| {
+            cursor.get_one::<HeaderMask<Header>>(num.into())
+        })
+    }
+
+    fn sealed_headers_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> RethResult<Vec<SealedHeader>> {
+        self.collect_range(self.query_range(range)?, |cursor, num| {
+            Ok(cursor
+                .get_two::<HeaderMask<Header, BlockHash>>(num.into())?
+                .map(|(header, hash)| header.seal(hash)))
+        })
+    }
+
+    fn sealed_header(&self, number: BlockNumber) -> RethResult<Option<SealedHeader>> {
+        self.ensure_covered_number(number)?;
+        Ok(self
+            .cursor()?
+            .get_two::<HeaderMask<Header, BlockHash>>(number.into())?
+            .map(|(header, hash)| header.seal(hash)))
+    }
+}
+
+impl<'a> BlockHashReader for SnapshotJarProvider<'a> {
+    fn block_hash(&self, number: u64) -> RethResult<Option<B256>> {
+        let headers = self.segment_provider(SnapshotSegment::Headers)?;
+        headers.ensure_covered_number(number)?;
+        headers.cursor()?.get_one::<HeaderMask<BlockHash>>(number.into())
+    }
+
+    fn canonical_hashes_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> RethResult<Vec<B256>> {
+        self.canonical_hashes_strided(start, end, 1, false)
+    }
+}
+
+impl<'a> BlockNumReader for SnapshotJarProvider<'a> {
+    fn chain_info(&self) -> RethResult<ChainInfo> {
+        // The highest block of the headers jar is the only one it knows of
+        let headers = self.segment_provider(SnapshotSegment::Headers)?;
+        let best_number = headers.best_block_number()?;
+        let best_hash = headers
+            .block_hash(best_number)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(best_number.into()))?;
+
+        Ok(ChainInfo { best_hash, best_number })
+    }
+
+    fn best_block_number(&self) -> RethResult<BlockNumber> {
+        self.last_block_number()
+    }
+
+    fn last_block_number(&self) -> RethResult<BlockNumber> {
+        // Only headers jars have a row per block of their range
+        Ok(*self.segment_provider(SnapshotSegment::Headers)?.block_range_inclusive().end())
+    }
+
+    fn block_number(&self, hash: B256) -> RethResult<Option<BlockNumber>> {
+        // Direct lookup through the jar filter and perfect hashing function, which reads a single
+        // row at most. The latter maps unknown hashes to some row too, so it has to match.
+        let headers = self.segment_provider(SnapshotSegment::Headers)?;
+        headers.metrics().record_point_query();
+        let mut cursor = headers.cursor()?;
+
+        Ok(cursor
+            .get_one::<HeaderMask<BlockHash>>((&hash).into())?
+            .and_then(|res| (res == hash).then(|| cursor.number() - 1)))
+    }
+}
+
+impl<'a> TransactionsProvider for SnapshotJarProvider<'a> {
+    fn transaction_id(&self, tx_hash: TxHash) -> RethResult<Option<TxNumber>> {
+        Ok(self.transaction_with_id_by_hash(tx_hash)?.map(|(num, _)| num))
+    }
+
+    fn transaction_by_id(&self, num: TxNumber) -> RethResult<Option<TransactionSigned>> {
+        Ok(self.transaction_by_id_no_hash(num)?.map(Into::into))
+    }
+
+    fn transaction_by_id_no_hash(
+        &self,
+        num: TxNumber,
+    ) -> RethResult<Option<TransactionSignedNoHash>> {
+        self.ensure_covered_number(num)?;
+        self.cursor()?.get_one::<TransactionMask<TransactionSignedNoHash>>(num.into())
+    }
+
+    fn transaction_by_hash(&self, hash: TxHash) -> RethResult<Option<TransactionSigned>> {
+        Ok(self.transaction_with_id_by_hash(hash)?.map(|(_, tx)| tx.with_hash()))
+    }
+
+    fn transaction_by_hash_with_meta(
+        &self,
+        hash: TxHash,
+    ) -> RethResult<Option<(TransactionSigned, TransactionMeta)>> {
+        let Some((num, transaction)) = self
+            .segment_provider(SnapshotSegment::Transactions)?
+            .transaction_with_id_by_hash(hash)?
+        else {
+            return Ok(None)
+        };
+        let Some((block_number, body)) = self.block_body_indices_by_tx(num)? else {
+            return Ok(None)
+        };
+        let Some(header) =
+            self.segment_provider(SnapshotSegment::Headers)?.sealed_header(block_number)?
+        else {
+            return Ok(None)
+        };
+
+        let meta = TransactionMeta {
+            tx_hash: hash,
+            index: num - body.first_tx_num,
+            block_hash: header.hash(),
+            block_number,
+            base_fee: header.base_fee_per_gas,
+            excess_blob_gas: header.excess_blob_gas,
+        };
+
+        Ok(Some((transaction.with_hash(), meta)))
+    }
+
+    fn transaction_block(&self, id: TxNumber) -> RethResult<Option<BlockNumber>> {
+        Ok(self.block_body_indices_by_tx(id)?.map(|(number, _)| number))
+    }
+
+    fn transactions_by_block(
+        &self,
+        block_id: BlockHashOrNumber,
+    ) -> RethResult<Option<Vec<TransactionSigned>>> {
+        // Hashes are first resolved to their number through the headers segment, so that both
+        // arms find the transactions of the block the same way
+        let Some(number) = self.block_number_of(block_id)? else { return Ok(None) };
+        let Some(body) = self.block_body_indices(number)? else { return Ok(None) };
+
+        let transactions = self
+            .segment_provider(SnapshotSegment::Transactions)?
+            .transactions_by_tx_range(body.tx_num_range())?;
+        if transactions.len() as u64 != body.tx_count() {
+            // Blocks whose transactions are missing from the jar are not found, while partial ones
+            // are corrupted.
+            if transactions.is_empty() {
+                return Ok(None)
+            }
+            return Err(ProviderError::BlockBodyTransactionCount.into())
+        }
+
+        Ok(Some(transactions.into_iter().map(|tx| tx.with_hash()).collect()))
+    }
+
+    fn transactions_by_block_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> RethResult<Vec<Vec<TransactionSigned>>> {
+        self.block_bodies_iter(range)?
+            .map(|body| body.map(|(_, transactions)| transactions))
+            .collect()
+    }
+
+    fn senders_by_tx_range(&self, range: impl RangeBounds<TxNumber>) -> RethResult<Vec<Address>> {
+        // Precomputed senders are read as they are, without recovering them
+        if let Ok(provider) = self.segment_provider(SnapshotSegment::Senders) {
+            return provider.collect_range(provider.query_range(range)?, |cursor, num| {
+                cursor.get_one::<SenderMask<Address>>(num.into())
+            })
+        }
+
+        // Senders are recovered in parallel once there are enough transactions to benefit from it
+        let txes = self.transactions_by_tx_range(range)?;
+        Ok(TransactionSignedNoHash::recover_signers(&txes, txes.len())
+            .ok_or(BlockExecutionError::Validation(BlockValidationError::SenderRecoveryError))?)
+    }
+
+    fn transactions_by_tx_range(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> RethResult<Vec<reth_primitives::TransactionSignedNoHash>> {
+        // Rows are decoded in a single pass, stopping at the first transaction missing from the
+        // jar like the other range reads
+        self.collect_range_batch(self.query_range(range)?, |cursor, numbers, transactions| {
+            cursor.get_many_with::<TransactionMask<TransactionSignedNoHash>>(
+                numbers,
+                |_, tx| {
+                    transactions.push(tx);
+                    Ok(())
+                },
+            )?;
+            Ok(())
+        })
+    }
+
+    fn transaction_sender(&self, id: TxNumber) -> RethResult<Option<Address>> {
+        // Precomputed senders are read as they are, without recovering them
+        if let Ok(provider) = self.segment_provider(SnapshotSegment::Senders) {
+            provider.ensure_covered_number(id)?;
+            return provider.cursor()?.get_one::<SenderMask<Address>>(id.into())
+        }
+
+        let Some(transaction) = self.transaction_by_id_no_hash(id)? else { return Ok(None) };
+        Ok(Some(
+            transaction.recover_signer().ok_or(BlockExecutionError::Validation(
+                BlockValidationError::SenderRecoveryError,
+            ))?,
+        ))
+    }
+}
+
+impl<'a> ReceiptProvider for SnapshotJarProvider<'a> {
+    fn receipt(&self, num: TxNumber) -> RethResult<Option<Receipt>> {
+        self.ensure_covered_number(num)?;
+        self.cursor()?.get_one::<ReceiptMask<Receipt>>(num.into())
+    }
+
+    fn receipt_by_hash(&self, hash: TxHash) -> RethResult<Option<Receipt>> {
+        // Transaction hashes are only indexed by the transactions segment, and the receipt may be
+        // held by any receipts jar of the chain
+        self.segment_provider(SnapshotSegment::Transactions)?;
+        for transactions in self.segment_providers(SnapshotSegment::Transactions) {
+            if let Some(num) = transactions.transaction_id(hash)? {
+                return match self.segment_provider_covering(SnapshotSegment::Receipts, num)? {
+                    Some(receipts) => receipts.receipt(num),
+                    None => Ok(None),
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn receipts_by_block(&self, block: BlockHashOrNumber) -> RethResult<Option<Vec<Receipt>>> {
+        let Some(number) = self.block_number_of(block)? else { return Ok(None) };
+        let Some(body) = self.block_body_indices(number)? else { return Ok(None) };
+
+        let receipts = self
+            .segment_provider(SnapshotSegment::Receipts)?
+            .receipts_by_tx_range(body.tx_num_range())?;
+        if receipts.len() as u64 != body.tx_count() {
+            // Blocks whose receipts are missing from the jar are not found, while partial ones are
+            // corrupted.
+            if receipts.is_empty() {
+                return Ok(None)
+            }
+            return Err(ProviderError::ReceiptNotFound(
+                (body.first_tx_num + receipts.len() as u64).into(),
+            )
+            .into())
+        }
+
+        Ok(Some(receipts))
+    }
+}
+
+/// Converts an inclusive range into a half-open one, which is empty if `range` is.
+fn to_half_open(range: &RangeInclusive<u64>) -> Range<u64> {
+    *range.start()..range.end().saturating_add(1).max(*range.start())
+}
+
+/// Returns the number of columns of the jars of `segment`.
+fn segment_columns(segment: SnapshotSegment) -> usize {
+    match segment {
+        SnapshotSegment::Headers => 3,
+        _ => 1,
+    }
+}
+
+/// Decodes a column value, describing why it failed otherwise.
+fn decode<T: Decompress>(value: &[u8]) -> Result<T, String> {
+    T::decompress(value).map_err(|err| err.to_string())
+}
+
+/// Converts `bounds` into a half-open range. Bounds that would go past [`u64::MAX`] are saturated
+/// to it.
 fn to_range<R: RangeBounds<u64>>(bounds: R) -> Range<u64> {
     let start = match bounds.start_bound() {
         std::ops::Bound::Included(&v) => v,
-        std::ops::Bound::Excluded(&v) => v + 1,
+        std::ops::Bound::Excluded(&v) => v.saturating_add(1),
         std::ops::Bound::Unbounded => 0,
     };
 
-    let end = match bounds.end_bound() {
-        std::ops::Bound::Included(&v) => v + 1,
-        std::ops::Bound::Excluded(&v) => v,
-        std::ops::Bound::Unbounded => u64::MAX,
-    };
+    let end = match bounds.end_bound() {
+        std::ops::Bound::Included(&v) => v.saturating_add(1),
+        std::ops::Bound::Excluded(&v) => v,
+        std::ops::Bound::Unbounded => u64::MAX,
+    };
+
+    start..end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::SnapshotProvider;
+    use assert_matches::assert_matches;
+    use reth_db::table::Compress;
+    use reth_interfaces::{
+        test_utils::generators::{self, random_header_range, random_receipt, random_signed_tx},
+        RethError,
+    };
+    use reth_nippy_jar::NippyJar;
+    use reth_primitives::{
+        snapshot::{SegmentHeader, BLOCKS_PER_SNAPSHOT},
+        Signature, Transaction, TxEip1559, TxEip2930, TxLegacy,
+    };
+    use std::path::Path;
+
+    /// Freezes a `segment` snapshot with the given columns of already compressed rows into `path`.
+    /// If there are `keys`, rows can also be queried by them.
+    fn create_jar(
+        path: &Path,
+        segment: SnapshotSegment,
+        block_range: RangeInclusive<BlockNumber>,
+        tx_range: RangeInclusive<TxNumber>,
+        columns: Vec<Vec<Vec<u8>>>,
+        keys: Vec<B256>,
+    ) {
+        let row_count = columns.first().map_or(0, |column| column.len());
+        let mut jar =
+            NippyJar::new(columns.len(), path, SegmentHeader::new(block_range, tx_range, segment));
+
+        if !keys.is_empty() {
+            jar = jar.with_cuckoo_filter(row_count + 10).with_fmph();
+            jar.prepare_index(keys.into_iter().map(Ok), row_count).unwrap();
+        }
+
+        jar.freeze(
+            columns
+                .into_iter()
+                .map(|column| column.into_iter().map(Ok).collect::<Vec<_>>())
+                .collect(),
+            row_count as u64,
+        )
+        .unwrap();
+    }
+
+    /// Creates a headers snapshot over `range` into `path`, returning the headers written to it.
+    fn create_headers_jar(path: &Path, range: RangeInclusive<BlockNumber>) -> Vec<SealedHeader> {
+        let headers = random_header_range(
+            &mut generators::rng(),
+            *range.start()..range.end() + 1,
+            B256::random(),
+        );
+        create_headers_jar_from(path, headers)
+    }
+
+    /// Creates a headers snapshot of `headers` into `path`, which must be consecutive blocks.
+    /// Returns them.
+    fn create_headers_jar_from(path: &Path, headers: Vec<SealedHeader>) -> Vec<SealedHeader> {
+        let range = headers[0].number..=headers[headers.len() - 1].number;
+
+        let mut td = U256::ZERO;
+        let mut columns = vec![vec![], vec![], vec![]];
+        for header in &headers {
+            td += header.difficulty;
+            columns[0].push(header.header.clone().compress());
+            columns[1].push(CompactU256::from(td).compress());
+            columns[2].push(header.hash().compress());
+        }
+
+        let hashes = headers.iter().map(|header| header.hash()).collect();
+        create_jar(path, SnapshotSegment::Headers, range.clone(), range, columns, hashes);
+        headers
+    }
+
+    /// Creates a receipts snapshot over `tx_range` into `path`, returning the receipts written to
+    /// it.
+    fn create_receipts_jar(path: &Path, tx_range: RangeInclusive<TxNumber>) -> Vec<Receipt> {
+        let mut rng = generators::rng();
+        let receipts = tx_range
+            .clone()
+            .map(|_| random_receipt(&mut rng, &random_signed_tx(&mut rng), Some(2)))
+            .collect::<Vec<_>>();
+        create_receipts_jar_from(path, tx_range, receipts)
+    }
+
+    /// Creates a receipts snapshot of `receipts` over `tx_range` into `path`, returning them.
+    fn create_receipts_jar_from(
+        path: &Path,
+        tx_range: RangeInclusive<TxNumber>,
+        receipts: Vec<Receipt>,
+    ) -> Vec<Receipt> {
+        create_jar(
+            path,
+            SnapshotSegment::Receipts,
+            0..=0,
+            tx_range,
+            vec![receipts.iter().map(|receipt| receipt.clone().compress()).collect()],
+            vec![],
+        );
+        receipts
+    }
+
+    /// Creates a transactions snapshot over `tx_range` into `path`, which can be queried by hash.
+    /// Returns the transactions written to it.
+    fn create_transactions_jar(
+        path: &Path,
+        tx_range: RangeInclusive<TxNumber>,
+    ) -> Vec<TransactionSigned> {
+        let mut rng = generators::rng();
+        let transactions = tx_range.clone().map(|_| random_signed_tx(&mut rng)).collect::<Vec<_>>();
+        create_transactions_jar_from(path, tx_range, transactions)
+    }
+
+    /// Creates a transactions snapshot of `transactions` over `tx_range` into `path`, which can be
+    /// queried by hash. Returns them.
+    fn create_transactions_jar_from(
+        path: &Path,
+        tx_range: RangeInclusive<TxNumber>,
+        transactions: Vec<TransactionSigned>,
+    ) -> Vec<TransactionSigned> {
+        create_jar(
+            path,
+            SnapshotSegment::Transactions,
+            0..=0,
+            tx_range,
+            vec![transactions
+                .iter()
+                .map(|tx| TransactionSignedNoHash::from(tx.clone()).compress())
+                .collect()],
+            transactions.iter().map(|tx| tx.hash()).collect(),
+        );
+        transactions
+    }
+
+    /// Creates a block body indices snapshot into `path` of consecutive blocks from `first_block`,
+    /// holding `tx_counts` transactions each from `first_tx` onwards. Returns the written indices.
+    fn create_block_body_indices_jar(
+        path: &Path,
+        first_block: BlockNumber,
+        first_tx: TxNumber,
+        tx_counts: &[u64],
+    ) -> Vec<StoredBlockBodyIndices> {
+        let mut next_tx = first_tx;
+        let bodies = tx_counts
+            .iter()
+            .map(|&tx_count| {
+                let body = StoredBlockBodyIndices { first_tx_num: next_tx, tx_count };
+                next_tx += tx_count;
+                body
+            })
+            .collect::<Vec<_>>();
+
+        create_jar(
+            path,
+            SnapshotSegment::BlockBodyIndices,
+            first_block..=first_block + tx_counts.len() as u64 - 1,
+            first_tx..=next_tx.saturating_sub(1),
+            vec![bodies.iter().map(|body| body.clone().compress()).collect()],
+            vec![],
+        );
+        bodies
+    }
+
+    /// Creates a senders snapshot over `tx_range` into `path` with the recovered senders of
+    /// `transactions`, returning them.
+    fn create_senders_jar(
+        path: &Path,
+        tx_range: RangeInclusive<TxNumber>,
+        transactions: &[TransactionSigned],
+    ) -> Vec<Address> {
+        let senders =
+            transactions.iter().map(|tx| tx.recover_signer().unwrap()).collect::<Vec<_>>();
+
+        create_jar(
+            path,
+            SnapshotSegment::Senders,
+            0..=0,
+            tx_range,
+            vec![senders.iter().map(|sender| sender.compress()).collect()],
+            vec![],
+        );
+        senders
+    }
+
+    /// Returns the headers of blocks 10 to 12 and the transactions of the first two, paying fees of
+    /// every kind. Only block 10 has a base fee, of 10.
+    ///
+    /// Block 10 holds a legacy, an EIP-2930 and two EIP-1559 transactions, the last one with its
+    /// priority fee capped by its fee cap. Block 11 holds a legacy transaction and block 12 none.
+    fn fee_blocks() -> (Vec<SealedHeader>, Vec<TransactionSigned>, Vec<u64>) {
+        let headers = [Some(10), None, None]
+            .into_iter()
+            .zip(10..)
+            .map(|(base_fee_per_gas, number)| {
+                Header { number, base_fee_per_gas, ..Default::default() }.seal_slow()
+            })
+            .collect();
+
+        let transactions = [
+            Transaction::Legacy(TxLegacy { gas_price: 25, ..Default::default() }),
+            Transaction::Eip2930(TxEip2930 { gas_price: 12, ..Default::default() }),
+            Transaction::Eip1559(TxEip1559 {
+                max_fee_per_gas: 100,
+                max_priority_fee_per_gas: 5,
+                ..Default::default()
+            }),
+            Transaction::Eip1559(TxEip1559 {
+                max_fee_per_gas: 13,
+                max_priority_fee_per_gas: 5,
+                ..Default::default()
+            }),
+            Transaction::Legacy(TxLegacy { gas_price: 7, ..Default::default() }),
+        ]
+        .into_iter()
+        .map(|tx| TransactionSigned::from_transaction_and_signature(tx, Signature::default()))
+        .collect();
+
+        (headers, transactions, vec![4, 1, 0])
+    }
+
+    /// Loads the snapshot of each segment at its path into `manager`.
+    ///
+    /// Jars need to be loaded beforehand when several of them are borrowed at once, since the
+    /// manager can't insert new ones while others are borrowed.
+    fn load_jars(manager: &SnapshotProvider, jars: &[(SnapshotSegment, BlockNumber, &Path)]) {
+        for (segment, block, path) in jars {
+            manager.get_segment_provider(*segment, *block, Some(path.to_path_buf())).unwrap();
+        }
+    }
+
+    #[test]
+    fn block_range_forms() {
+        for range in [0..=99, 500_000..=500_099, 7..=7] {
+            let snap_file = tempfile::NamedTempFile::new().unwrap();
+            let headers = create_headers_jar(snap_file.path(), range.clone());
+
+            let manager = SnapshotProvider::default();
+            let jar = manager
+                .get_segment_provider(
+                    SnapshotSegment::Headers,
+                    *range.start(),
+                    Some(snap_file.path().into()),
+                )
+                .unwrap();
+
+            let half_open = jar.block_range();
+            let inclusive = jar.block_range_inclusive();
+            assert_eq!(inclusive, range);
+            assert_eq!(half_open.start, *inclusive.start());
+            assert_eq!(half_open.end, inclusive.end() + 1);
+            assert!(half_open.clone().eq(inclusive.clone()));
+            assert_eq!(half_open.clone().count(), headers.len());
+
+            // The inclusive end is the last block held by the jar, the half-open one is not.
+            assert!(jar.header_by_number(*inclusive.end()).unwrap().is_some());
+            assert_matches!(
+                jar.header_by_number(half_open.end),
+                Err(RethError::Provider(ProviderError::SnapshotOutOfRange { .. }))
+            );
+        }
+    }
+
+    #[test]
+    fn receipts_by_tx_range_into_appends() {
+        let snap_file = tempfile::NamedTempFile::new().unwrap();
+        let receipts = create_receipts_jar(snap_file.path(), 100..=149);
+
+        let manager = SnapshotProvider::default();
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Receipts, 0, Some(snap_file.path().into()))
+            .unwrap();
+
+        let mut out = vec![receipts[0].clone()];
+        jar.receipts_by_tx_range_into(110..120, &mut out).unwrap();
+        jar.receipts_by_tx_range_into(140.., &mut out).unwrap();
+
+        // Pre-existing content is kept and each range is appended after it.
+        assert_eq!(out[0], receipts[0]);
+        assert_eq!(out[1..11], jar.receipts_by_tx_range(110..120).unwrap());
+        assert_eq!(out[11..], jar.receipts_by_tx_range(140..).unwrap());
+        assert_eq!(out[1..11], receipts[10..20]);
+        assert_eq!(out[11..], receipts[40..]);
+
+        // Ranges are cut short at the first receipt missing from the jar, and those past it
+        // belong to another one.
+        assert_eq!(jar.receipts_by_tx_range(100..).unwrap(), receipts);
+        assert_matches!(
+            jar.receipts_by_tx_range(150..160),
+            Err(RethError::Provider(ProviderError::SnapshotOutOfRange { .. }))
+        );
+    }
+
+    #[test]
+    fn binary_search_jars() {
+        let manager = SnapshotProvider::default();
+        let snap_files = [0..=9, 500_000..=500_009, 1_000_000..=1_000_009].map(|range| {
+            let snap_file = tempfile::NamedTempFile::new().unwrap();
+            create_headers_jar(snap_file.path(), range.clone());
+            (snap_file, range)
+        });
+
+        // Loads every jar beforehand, since the manager can't insert while jars are borrowed.
+        for (snap_file, range) in &snap_files {
+            manager
+                .get_segment_provider(
+                    SnapshotSegment::Headers,
+                    *range.start(),
+                    Some(snap_file.path().into()),
+                )
+                .unwrap();
+        }
+
+        let jars = snap_files
+            .iter()
+            .map(|(_, range)| {
+                manager
+                    .get_segment_provider(SnapshotSegment::Headers, *range.start(), None)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        assert!(jars[0] < jars[1] && jars[1] < jars[2]);
+        assert_eq!(jars[1].partial_cmp(&jars[1]), Some(Ordering::Equal));
+
+        for (index, (_, range)) in snap_files.iter().enumerate() {
+            for num in [*range.start(), *range.end()] {
+                assert!(jars[index].contains_block(num));
+                assert_eq!(jars.binary_search_by(|jar| jar.cmp_block(num)), Ok(index));
+            }
+        }
+
+        // Blocks in between jars or after the last one are not owned by any.
+        assert_eq!(jars.binary_search_by(|jar| jar.cmp_block(10)), Err(1));
+        assert_eq!(jars.binary_search_by(|jar| jar.cmp_block(1_000_010)), Err(3));
+        assert!(!jars[0].contains_block(10));
+    }
+
+    #[test]
+    fn reload_bumps_epoch() {
+        let snap_file = tempfile::NamedTempFile::new().unwrap();
+        create_headers_jar(snap_file.path(), 0..=9);
+
+        let manager = SnapshotProvider::default();
+        let epoch = manager
+            .get_segment_provider(SnapshotSegment::Headers, 0, Some(snap_file.path().into()))
+            .unwrap()
+            .epoch();
+        assert!(!manager.is_stale(SnapshotSegment::Headers, 0, epoch));
+        assert!(manager.is_stale(SnapshotSegment::Headers, BLOCKS_PER_SNAPSHOT, epoch));
+
+        // Replace the jar with one holding different headers.
+        let new_snap_file = tempfile::NamedTempFile::new().unwrap();
+        let headers = create_headers_jar(new_snap_file.path(), 0..=9);
+        let jar = manager
+            .reload_segment_provider(SnapshotSegment::Headers, 0, new_snap_file.path())
+            .unwrap();
+        assert_ne!(jar.epoch(), epoch);
+        assert_eq!(jar.header_by_number(0).unwrap(), Some(headers[0].header.clone()));
+        drop(jar);
+
+        assert!(manager.is_stale(SnapshotSegment::Headers, 0, epoch));
+    }
+
+    #[test]
+    fn reload_while_holding_provider() {
+        let snap_file = tempfile::NamedTempFile::new().unwrap();
+        let old_headers = create_headers_jar(snap_file.path(), 0..=9);
+
+        let manager = SnapshotProvider::default();
+        let old = manager
+            .get_segment_provider(SnapshotSegment::Headers, 0, Some(snap_file.path().into()))
+            .unwrap();
+        assert!(!manager.is_stale(SnapshotSegment::Headers, 0, old.epoch()));
+
+        // Reloading doesn't wait for the providers of the replaced jar.
+        let new_snap_file = tempfile::NamedTempFile::new().unwrap();
+        let new_headers = create_headers_jar(new_snap_file.path(), 0..=9);
+        let new = manager
+            .reload_segment_provider(SnapshotSegment::Headers, 0, new_snap_file.path())
+            .unwrap();
+
+        // The held provider is detected as stale, while still serving the jar it had.
+        assert!(manager.is_stale(SnapshotSegment::Headers, 0, old.epoch()));
+        assert!(!manager.is_stale(SnapshotSegment::Headers, 0, new.epoch()));
+        assert_eq!(old.header_by_number(3).unwrap(), Some(old_headers[3].header.clone()));
+        assert_eq!(new.header_by_number(3).unwrap(), Some(new_headers[3].header.clone()));
+    }
+
+    #[test]
+    fn genesis_hash_of_base_jar() {
+        let manager = SnapshotProvider::default();
+
+        let base_file = tempfile::NamedTempFile::new().unwrap();
+        let headers = create_headers_jar(base_file.path(), 0..=9);
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Headers, 0, Some(base_file.path().into()))
+            .unwrap();
+        assert!(jar.is_base());
+        assert_eq!(jar.genesis_hash().unwrap(), Some(headers[0].hash()));
+        drop(jar);
+
+        let snap_file = tempfile::NamedTempFile::new().unwrap();
+        create_headers_jar(snap_file.path(), BLOCKS_PER_SNAPSHOT..=BLOCKS_PER_SNAPSHOT + 9);
+        let jar = manager
+            .get_segment_provider(
+                SnapshotSegment::Headers,
+                BLOCKS_PER_SNAPSHOT,
+                Some(snap_file.path().into()),
+            )
+            .unwrap();
+        assert!(!jar.is_base());
+        assert_eq!(jar.genesis_hash().unwrap(), None);
+    }
+
+    #[test]
+    fn cursor_number_position() {
+        let snap_file = tempfile::NamedTempFile::new().unwrap();
+        create_headers_jar(snap_file.path(), 100..=109);
+
+        let manager = SnapshotProvider::default();
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Headers, 100, Some(snap_file.path().into()))
+            .unwrap();
+        let mut cursor = jar.cursor().unwrap();
+        assert_eq!(cursor.number(), 100);
+
+        // Reading a row moves past it.
+        assert!(cursor.get_one::<HeaderMask<Header>>(105.into()).unwrap().is_some());
+        assert_eq!(cursor.number(), 106);
+
+        // Numbers before the jar leave it as is, while those after its end are pointed at.
+        assert!(cursor.get_one::<HeaderMask<Header>>(99.into()).unwrap().is_none());
+        assert_eq!(cursor.number(), 106);
+        assert!(cursor.get_one::<HeaderMask<Header>>(110.into()).unwrap().is_none());
+        assert_eq!(cursor.number(), 110);
+    }
+
+    #[test]
+    fn receipts_by_block_with_auxiliar() {
+        let receipts_file = tempfile::NamedTempFile::new().unwrap();
+        let bodies_file = tempfile::NamedTempFile::new().unwrap();
+        let receipts = create_receipts_jar(receipts_file.path(), 0..=5);
+        let bodies = create_block_body_indices_jar(bodies_file.path(), 0, 0, &[2, 0, 3, 1]);
+
+        let manager = SnapshotProvider::default();
+        load_jars(
+            &manager,
+            &[
+                (SnapshotSegment::Receipts, 0, receipts_file.path()),
+                (SnapshotSegment::BlockBodyIndices, 0, bodies_file.path()),
+            ],
+        );
+        let jar = manager.get_segment_provider(SnapshotSegment::Receipts, 0, None).unwrap();
+
+        // Blocks can't be resolved without the block body indices.
+        assert_matches!(
+            jar.receipts_by_block(0.into()),
+            Err(RethError::Provider(ProviderError::UnsupportedProvider))
+        );
+
+        let jar = jar.with_auxiliar(
+            manager.get_segment_provider(SnapshotSegment::BlockBodyIndices, 0, None).unwrap(),
+        );
+        for (number, body) in bodies.iter().enumerate() {
+            let range = body.first_tx_num as usize..body.next_tx_num() as usize;
+            assert_eq!(
+                jar.receipts_by_block((number as u64).into()).unwrap(),
+                Some(receipts[range].to_vec())
+            );
+        }
+        assert_eq!(jar.receipts_by_block(1.into()).unwrap(), Some(vec![]));
+        assert_matches!(
+            jar.receipts_by_block(4.into()),
+            Err(RethError::Provider(ProviderError::SnapshotOutOfRange { .. }))
+        );
+    }
+
+    #[test]
+    fn transactions_by_block_with_auxiliar() {
+        let transactions_file = tempfile::NamedTempFile::new().unwrap();
+        let bodies_file = tempfile::NamedTempFile::new().unwrap();
+        let transactions = create_transactions_jar(transactions_file.path(), 0..=4);
+        create_block_body_indices_jar(bodies_file.path(), 0, 0, &[0, 2, 0, 3]);
+
+        let manager = SnapshotProvider::default();
+        load_jars(
+            &manager,
+            &[
+                (SnapshotSegment::Transactions, 0, transactions_file.path()),
+                (SnapshotSegment::BlockBodyIndices, 0, bodies_file.path()),
+            ],
+        );
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Transactions, 0, None)
+            .unwrap()
+            .with_auxiliar(
+                manager.get_segment_provider(SnapshotSegment::BlockBodyIndices, 0, None).unwrap(),
+            );
+
+        // First block of the jar is empty, while the last one is not.
+        assert_eq!(jar.transactions_by_block(0.into()).unwrap(), Some(vec![]));
+        assert_eq!(jar.transactions_by_block(2.into()).unwrap(), Some(vec![]));
+        assert_eq!(jar.transactions_by_block(1.into()).unwrap(), Some(transactions[..2].to_vec()));
+        assert_eq!(jar.transactions_by_block(3.into()).unwrap(), Some(transactions[2..].to_vec()));
+        assert_matches!(
+            jar.transactions_by_block(4.into()),
+            Err(RethError::Provider(ProviderError::SnapshotOutOfRange { .. }))
+        );
+
+        assert_eq!(
+            jar.transactions_by_block_range(0..=3).unwrap(),
+            vec![vec![], transactions[..2].to_vec(), vec![], transactions[2..].to_vec()]
+        );
+        assert_eq!(
+            jar.transactions_by_block_range(2..10).unwrap(),
+            vec![vec![], transactions[2..].to_vec()]
+        );
+    }
+
+    #[test]
+    fn transaction_block_at_block_transitions() {
+        let receipts_file = tempfile::NamedTempFile::new().unwrap();
+        let bodies_file = tempfile::NamedTempFile::new().unwrap();
+        create_receipts_jar(receipts_file.path(), 100..=105);
+        create_block_body_indices_jar(bodies_file.path(), 10, 100, &[2, 0, 3, 1]);
+
+        let manager = SnapshotProvider::default();
+        load_jars(
+            &manager,
+            &[
+                (SnapshotSegment::Receipts, 0, receipts_file.path()),
+                (SnapshotSegment::BlockBodyIndices, 0, bodies_file.path()),
+            ],
+        );
+        let jar = manager.get_segment_provider(SnapshotSegment::Receipts, 0, None).unwrap();
+
+        // The index is required.
+        assert_matches!(
+            jar.transaction_block(100),
+            Err(RethError::Provider(ProviderError::UnsupportedProvider))
+        );
+
+        let jar = jar.with_auxiliar(
+            manager.get_segment_provider(SnapshotSegment::BlockBodyIndices, 0, None).unwrap(),
+        );
+        for (tx, block) in [
+            (99, None),
+            (100, Some(10)),
+            (101, Some(10)),
+            (102, Some(12)),
+            (104, Some(12)),
+            (105, Some(13)),
+            (106, None),
+        ] {
+            assert_eq!(jar.transaction_block(tx).unwrap(), block, "tx {tx}");
+        }
+    }
+
+    #[test]
+    fn transaction_by_hash_with_meta_index() {
+        let headers_file = tempfile::NamedTempFile::new().unwrap();
+        let transactions_file = tempfile::NamedTempFile::new().unwrap();
+        let bodies_file = tempfile::NamedTempFile::new().unwrap();
+        let headers = create_headers_jar(headers_file.path(), 0..=3);
+        let transactions = create_transactions_jar(transactions_file.path(), 0..=5);
+        create_block_body_indices_jar(bodies_file.path(), 0, 0, &[2, 0, 3, 1]);
+
+        let manager = SnapshotProvider::default();
+        load_jars(
+            &manager,
+            &[
+                (SnapshotSegment::Headers, 0, headers_file.path()),
+                (SnapshotSegment::Transactions, 0, transactions_file.path()),
+                (SnapshotSegment::BlockBodyIndices, 0, bodies_file.path()),
+            ],
+        );
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Headers, 0, None)
+            .unwrap()
+            .with_auxiliar(
+                manager.get_segment_provider(SnapshotSegment::Transactions, 0, None).unwrap(),
+            )
+            .with_auxiliar(
+                manager.get_segment_provider(SnapshotSegment::BlockBodyIndices, 0, None).unwrap(),
+            );
+
+        // Second transaction of block 2.
+        let (transaction, meta) =
+            jar.transaction_by_hash_with_meta(transactions[3].hash()).unwrap().unwrap();
+        assert_eq!(transaction, transactions[3]);
+        assert_eq!(
+            meta,
+            TransactionMeta {
+                tx_hash: transactions[3].hash(),
+                index: 1,
+                block_hash: headers[2].hash(),
+                block_number: 2,
+                base_fee: headers[2].base_fee_per_gas,
+                excess_blob_gas: headers[2].excess_blob_gas,
+            }
+        );
+
+        assert_eq!(jar.transaction_by_hash_with_meta(B256::random()).unwrap(), None);
+    }
+
+    #[test]
+    fn transactions_iter_is_lazy() {
+        let snap_file = tempfile::NamedTempFile::new().unwrap();
+        let transactions = create_transactions_jar(snap_file.path(), 0..=5);
+
+        let manager = SnapshotProvider::default();
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Transactions, 0, Some(snap_file.path().into()))
+            .unwrap();
+
+        // An unbounded range is only read as far as it's consumed.
+        let mut iter = jar.transactions_iter(0..).unwrap();
+        for tx in &transactions[..3] {
+            assert_eq!(iter.next().unwrap().unwrap().with_hash(), *tx);
+        }
+        drop(iter);
+
+        // It ends at the first missing transaction, like the allocating form.
+        let iterated = jar.transactions_iter(2..100).unwrap().collect::<RethResult<Vec<_>>>();
+        assert_eq!(iterated.unwrap(), jar.transactions_by_tx_range(2..100).unwrap());
+        assert_eq!(jar.transactions_iter(2..100).unwrap().count(), 4);
+
+        // Cut into the last two transactions, so that reading them would go out of bounds.
+        let truncated_file = tempfile::NamedTempFile::new().unwrap();
+        create_transactions_jar_from(truncated_file.path(), 0..=5, transactions.clone());
+        let last_len = TransactionSignedNoHash::from(transactions[5].clone()).compress().len();
+        let file = std::fs::OpenOptions::new().write(true).open(truncated_file.path()).unwrap();
+        file.set_len(file.metadata().unwrap().len() - last_len as u64 - 1).unwrap();
+
+        let manager = SnapshotProvider::default();
+        let jar = manager
+            .get_segment_provider(
+                SnapshotSegment::Transactions,
+                0,
+                Some(truncated_file.path().into()),
+            )
+            .unwrap();
+        assert_matches!(
+            jar.verify(),
+            Err(RethError::Provider(ProviderError::CorruptedSnapshot { number: 4, .. }))
+        );
+
+        // Consuming the first transactions leaves the rows after them unread.
+        let iterated = jar.transactions_iter(..).unwrap().take(4).collect::<RethResult<Vec<_>>>();
+        assert_eq!(
+            iterated.unwrap().into_iter().map(|tx| tx.with_hash()).collect::<Vec<_>>(),
+            transactions[..4]
+        );
+    }
+
+    #[test]
+    fn transaction_hashes_match_transactions() {
+        let snap_file = tempfile::NamedTempFile::new().unwrap();
+        create_transactions_jar(snap_file.path(), 10..=19);
+
+        let manager = SnapshotProvider::default();
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Transactions, 0, Some(snap_file.path().into()))
+            .unwrap();
+
+        let hashes = jar.transaction_hashes_by_tx_range(12..16).unwrap();
+        assert_eq!(
+            hashes,
+            (12..16)
+                .map(|num| (num, jar.transaction_by_id(num).unwrap().unwrap().hash()))
+                .collect::<Vec<_>>()
+        );
+
+        // Numbers beyond the end of the jar are left out, as for the transactions themselves.
+        let hashes = jar.transaction_hashes_by_tx_range(15..100).unwrap();
+        assert_eq!(hashes.len(), jar.transactions_by_tx_range(15..100).unwrap().len());
+        assert_eq!(hashes.last().map(|(num, _)| *num), Some(19));
+    }
+
+    #[test]
+    fn reverse_ranges() {
+        let headers_file = tempfile::NamedTempFile::new().unwrap();
+        let transactions_file = tempfile::NamedTempFile::new().unwrap();
+        create_headers_jar(headers_file.path(), 10..=19);
+        create_transactions_jar(transactions_file.path(), 100..=109);
+
+        let manager = SnapshotProvider::default();
+        load_jars(
+            &manager,
+            &[
+                (SnapshotSegment::Headers, 0, headers_file.path()),
+                (SnapshotSegment::Transactions, 0, transactions_file.path()),
+            ],
+        );
+        let headers = manager.get_segment_provider(SnapshotSegment::Headers, 0, None).unwrap();
+        let transactions =
+            manager.get_segment_provider(SnapshotSegment::Transactions, 0, None).unwrap();
+
+        for range in [10..20, 12..15, 15..30, 19..20] {
+            let mut forward = headers.headers_range(range.clone()).unwrap();
+            forward.reverse();
+            assert_eq!(headers.headers_range_rev(range.clone()).unwrap(), forward);
+
+            let range = range.start + 90..range.end + 90;
+            let mut forward = transactions.transactions_by_tx_range(range.clone()).unwrap();
+            forward.reverse();
+            assert_eq!(transactions.transactions_by_tx_range_rev(range).unwrap(), forward);
+        }
+
+        assert_matches!(
+            headers.headers_range_rev(20..30),
+            Err(RethError::Provider(ProviderError::SnapshotOutOfRange { .. }))
+        );
+        assert_matches!(
+            transactions.transactions_by_tx_range_rev(110..120),
+            Err(RethError::Provider(ProviderError::SnapshotOutOfRange { .. }))
+        );
+    }
+
+    #[test]
+    fn to_range_bounds() {
+        use std::ops::Bound::{Excluded, Included, Unbounded};
+
+        // Every combination of start and end bounds.
+        assert_eq!(to_range((Included(1), Included(5))), 1..6);
+        assert_eq!(to_range((Included(1), Excluded(5))), 1..5);
+        assert_eq!(to_range((Included(1), Unbounded)), 1..u64::MAX);
+        assert_eq!(to_range((Excluded(1), Included(5))), 2..6);
+        assert_eq!(to_range((Excluded(1), Excluded(5))), 2..5);
+        assert_eq!(to_range((Excluded(1), Unbounded)), 2..u64::MAX);
+        assert_eq!(to_range((Unbounded, Included(5))), 0..6);
+        assert_eq!(to_range((Unbounded, Excluded(5))), 0..5);
+        assert_eq!(to_range::<(std::ops::Bound<u64>, _)>((Unbounded, Unbounded)), 0..u64::MAX);
+
+        // Bounds at `u64::MAX` saturate instead of wrapping around.
+        assert_eq!(to_range(..=u64::MAX), 0..u64::MAX);
+        assert_eq!(to_range((Included(u64::MAX), Included(u64::MAX))), u64::MAX..u64::MAX);
+        assert_eq!(to_range((Excluded(u64::MAX), Unbounded)), u64::MAX..u64::MAX);
+        assert_eq!(to_range((Excluded(u64::MAX), Included(u64::MAX))), u64::MAX..u64::MAX);
+        assert_eq!(to_range((Excluded(u64::MAX - 1), Excluded(u64::MAX))), u64::MAX..u64::MAX);
+    }
+
+    #[test]
+    fn unbounded_ranges_return_jar_contents() {
+        let snap_file = tempfile::NamedTempFile::new().unwrap();
+        let headers = create_headers_jar(snap_file.path(), 10..=19);
+
+        let manager = SnapshotProvider::default();
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Headers, 10, Some(snap_file.path().into()))
+            .unwrap();
+
+        let expected = headers.iter().map(|header| header.header.clone()).collect::<Vec<_>>();
+        assert_eq!(jar.headers_range(..).unwrap(), expected);
+        assert_eq!(jar.headers_range(5..).unwrap(), expected);
+        assert_eq!(jar.headers_range(..=u64::MAX).unwrap(), expected);
+        assert_eq!(jar.sealed_headers_range(..).unwrap(), headers);
+        assert_eq!(
+            jar.canonical_hashes_range(0, u64::MAX).unwrap(),
+            headers.iter().map(|header| header.hash()).collect::<Vec<_>>()
+        );
+        assert_matches!(
+            jar.headers_range(20..),
+            Err(RethError::Provider(ProviderError::SnapshotOutOfRange { .. }))
+        );
+    }
+
+    #[test]
+    fn headers_range_exact_reports_missing() {
+        let snap_file = tempfile::NamedTempFile::new().unwrap();
+        let headers = create_headers_jar(snap_file.path(), 10..=19);
+
+        let manager = SnapshotProvider::default();
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Headers, 10, Some(snap_file.path().into()))
+            .unwrap();
+
+        // Fully present
+        assert_eq!(
+            jar.headers_range_exact(12..=15).unwrap(),
+            headers[2..=5].iter().map(|header| header.header.clone()).collect::<Vec<_>>()
+        );
+
+        // Rows are contiguous within a jar, so holes can only be outside of it: before its first
+        // block or past its last one.
+        assert_matches!(
+            jar.headers_range_exact(8..12),
+            Err(RethError::Provider(ProviderError::HeaderNotFound(BlockHashOrNumber::Number(8))))
+        );
+        assert_matches!(
+            jar.headers_range_exact(15..25),
+            Err(RethError::Provider(ProviderError::HeaderNotFound(BlockHashOrNumber::Number(20))))
+        );
+        assert_matches!(
+            jar.headers_range_exact(15..),
+            Err(RethError::Provider(ProviderError::HeaderNotFound(BlockHashOrNumber::Number(20))))
+        );
+
+        // The lenient form returns what's there.
+        assert_eq!(jar.headers_range(15..25).unwrap().len(), 5);
+    }
+
+    #[test]
+    fn senders_by_tx_range_recovery() {
+        let snap_file = tempfile::NamedTempFile::new().unwrap();
+        let transactions = create_transactions_jar(snap_file.path(), 0..=99);
+
+        let manager = SnapshotProvider::default();
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Transactions, 0, Some(snap_file.path().into()))
+            .unwrap();
+
+        let sequential =
+            transactions.iter().map(|tx| tx.recover_signer().unwrap()).collect::<Vec<_>>();
+
+        // Senders are only recovered in parallel by pools of several threads, from 10 transactions
+        // on for up to 8 threads. Single-threaded pools always recover them sequentially.
+        for threads in [1, 4] {
+            let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+            pool.install(|| {
+                assert_eq!(jar.senders_by_tx_range(..).unwrap(), sequential);
+                assert_eq!(jar.senders_by_tx_range(10..12).unwrap(), sequential[10..12]);
+            });
+        }
+    }
+
+    #[test]
+    fn covered_ranges_match_cursor() {
+        let headers_file = tempfile::NamedTempFile::new().unwrap();
+        let transactions_file = tempfile::NamedTempFile::new().unwrap();
+        let bodies_file = tempfile::NamedTempFile::new().unwrap();
+        create_headers_jar(headers_file.path(), 10..=19);
+        create_transactions_jar(transactions_file.path(), 100..=149);
+        create_block_body_indices_jar(bodies_file.path(), 10, 100, &[0, 0]);
+
+        let manager = SnapshotProvider::default();
+        load_jars(
+            &manager,
+            &[
+                (SnapshotSegment::Headers, 0, headers_file.path()),
+                (SnapshotSegment::Transactions, 0, transactions_file.path()),
+                (SnapshotSegment::BlockBodyIndices, 0, bodies_file.path()),
+            ],
+        );
+
+        let headers = manager.get_segment_provider(SnapshotSegment::Headers, 0, None).unwrap();
+        let range = headers.block_range();
+        assert_eq!(range, 10..20);
+        assert_eq!(headers.headers_range(range.clone()).unwrap().len(), range.clone().count());
+        assert!(headers.header_by_number(range.start).unwrap().is_some());
+        assert_matches!(
+            headers.header_by_number(range.end),
+            Err(RethError::Provider(ProviderError::SnapshotOutOfRange { .. }))
+        );
+
+        let transactions =
+            manager.get_segment_provider(SnapshotSegment::Transactions, 0, None).unwrap();
+        let range = transactions.tx_range();
+        assert_eq!(range, 100..150);
+        assert_eq!(transactions.transactions_by_tx_range(..).unwrap().len(), range.clone().count());
+        assert!(transactions.transaction_by_id_no_hash(range.start).unwrap().is_some());
+        assert_matches!(
+            transactions.transaction_by_id_no_hash(range.end),
+            Err(RethError::Provider(ProviderError::SnapshotOutOfRange { .. }))
+        );
+
+        // Blocks without transactions cover none.
+        let bodies =
+            manager.get_segment_provider(SnapshotSegment::BlockBodyIndices, 0, None).unwrap();
+        assert_eq!(bodies.block_range(), 10..12);
+        assert!(bodies.tx_range().is_empty());
+    }
+
+    #[test]
+    fn receipts_range_reads() {
+        let snap_file = tempfile::NamedTempFile::new().unwrap();
+        let receipts = create_receipts_jar(snap_file.path(), 100..=149);
+
+        let manager = SnapshotProvider::default();
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Receipts, 0, Some(snap_file.path().into()))
+            .unwrap();
+
+        // Dense range
+        assert_eq!(jar.receipts_by_tx_range(110..=120).unwrap(), receipts[10..=20]);
+        assert_eq!(
+            jar.receipts_iter(110..=120).unwrap().collect::<RethResult<Vec<_>>>().unwrap(),
+            receipts[10..=20]
+        );
+
+        // Range running past the end of the segment
+        assert_eq!(jar.receipts_by_tx_range(140..200).unwrap(), receipts[40..]);
+        assert_eq!(jar.receipts_iter(140..200).unwrap().count(), 10);
+        assert_eq!(jar.receipts_iter(0..).unwrap().count(), receipts.len());
+    }
+
+    #[test]
+    fn chain_info_from_top_header() {
+        let headers_file = tempfile::NamedTempFile::new().unwrap();
+        let receipts_file = tempfile::NamedTempFile::new().unwrap();
+        let headers = create_headers_jar(headers_file.path(), 10..=19);
+        create_receipts_jar(receipts_file.path(), 0..=5);
+
+        let manager = SnapshotProvider::default();
+        load_jars(
+            &manager,
+            &[
+                (SnapshotSegment::Headers, 10, headers_file.path()),
+                (SnapshotSegment::Receipts, 0, receipts_file.path()),
+            ],
+        );
+        let jar = manager.get_segment_provider(SnapshotSegment::Headers, 10, None).unwrap();
+
+        assert_eq!(jar.last_block_number().unwrap(), jar.block_range().end - 1);
+        assert_eq!(jar.best_block_number().unwrap(), 19);
+        assert_eq!(
+            jar.chain_info().unwrap(),
+            ChainInfo { best_hash: headers[9].hash(), best_number: 19 }
+        );
+
+        // Other jars don't know of the chain by themselves, only through a headers auxiliar.
+        let receipts = || manager.get_segment_provider(SnapshotSegment::Receipts, 0, None).unwrap();
+        assert_matches!(
+            receipts().chain_info(),
+            Err(RethError::Provider(ProviderError::UnsupportedProvider))
+        );
+        assert_matches!(
+            receipts().last_block_number(),
+            Err(RethError::Provider(ProviderError::UnsupportedProvider))
+        );
+        assert_matches!(
+            receipts().block_hash(3),
+            Err(RethError::Provider(ProviderError::UnsupportedProvider))
+        );
+
+        let receipts = receipts().with_auxiliar(jar);
+        assert_eq!(receipts.best_block_number().unwrap(), 19);
+        assert_eq!(
+            receipts.chain_info().unwrap(),
+            ChainInfo { best_hash: headers[9].hash(), best_number: 19 }
+        );
+        assert_eq!(receipts.block_number(headers[3].hash()).unwrap(), Some(13));
+    }
+
+    #[test]
+    fn multiple_auxiliars() {
+        let receipts_file = tempfile::NamedTempFile::new().unwrap();
+        let transactions_file = tempfile::NamedTempFile::new().unwrap();
+        let bodies_file = tempfile::NamedTempFile::new().unwrap();
+        let receipts = create_receipts_jar(receipts_file.path(), 0..=5);
+        let transactions = create_transactions_jar(transactions_file.path(), 0..=5);
+        create_block_body_indices_jar(bodies_file.path(), 0, 0, &[2, 0, 3, 1]);
+
+        let manager = SnapshotProvider::default();
+        load_jars(
+            &manager,
+            &[
+                (SnapshotSegment::Receipts, 0, receipts_file.path()),
+                (SnapshotSegment::Transactions, 0, transactions_file.path()),
+                (SnapshotSegment::BlockBodyIndices, 0, bodies_file.path()),
+            ],
+        );
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Receipts, 0, None)
+            .unwrap()
+            .with_auxiliar(
+                manager.get_segment_provider(SnapshotSegment::Transactions, 0, None).unwrap(),
+            )
+            .with_auxiliar(
+                manager.get_segment_provider(SnapshotSegment::BlockBodyIndices, 0, None).unwrap(),
+            );
+
+        // Transaction hashes are resolved through the transactions auxiliar.
+        assert_eq!(jar.receipt_by_hash(transactions[4].hash()).unwrap(), Some(receipts[4].clone()));
+
+        // Blocks are resolved through the block body indices auxiliar.
+        assert_eq!(jar.transaction_block(4).unwrap(), Some(2));
+        assert_eq!(jar.receipts_by_block(2.into()).unwrap(), Some(receipts[2..5].to_vec()));
+    }
+
+    #[test]
+    fn hash_lookups_are_direct() {
+        for rows in [10, 1000] {
+            let snap_file = tempfile::NamedTempFile::new().unwrap();
+            let headers = create_headers_jar(snap_file.path(), 0..=rows - 1);
+
+            let manager = SnapshotProvider::default();
+            let jar = manager
+                .get_segment_provider(SnapshotSegment::Headers, 0, Some(snap_file.path().into()))
+                .unwrap();
+            let mut cursor = jar.cursor().unwrap();
+
+            // A hit reads only its own row, wherever it is in the jar.
+            for header in [&headers[0], &headers[rows as usize / 2], headers.last().unwrap()] {
+                assert_eq!(jar.block_number(header.hash()).unwrap(), Some(header.number));
+
+                let hash = header.hash();
+                assert_eq!(
+                    cursor.get_one::<HeaderMask<BlockHash>>((&hash).into()).unwrap(),
+                    Some(hash)
+                );
+                assert_eq!(cursor.number(), header.number + 1);
+            }
+
+            // A miss is answered without scanning the jar.
+            for _ in 0..10 {
+                let hash = B256::random();
+                assert_eq!(jar.block_number(hash).unwrap(), None);
+
+                let number = cursor.number();
+                let row = cursor.get_one::<HeaderMask<BlockHash>>((&hash).into()).unwrap();
+                // Filtered out, or mapped to a single row by the perfect hashing function.
+                assert!(
+                    (row.is_none() && cursor.number() == number) ||
+                        row.is_some_and(|row| row != hash)
+                );
+            }
+
+            // Same for transactions.
+            let snap_file = tempfile::NamedTempFile::new().unwrap();
+            let transactions = create_transactions_jar(snap_file.path(), 0..=rows - 1);
+            let jar = manager
+                .get_segment_provider(
+                    SnapshotSegment::Transactions,
+                    0,
+                    Some(snap_file.path().into()),
+                )
+                .unwrap();
+            let mut cursor = jar.cursor().unwrap();
+
+            for num in [0, rows / 2, rows - 1] {
+                let transaction = &transactions[num as usize];
+                assert_eq!(jar.transaction_id(transaction.hash()).unwrap(), Some(num));
+                assert_eq!(
+                    jar.transaction_by_hash(transaction.hash()).unwrap().as_ref(),
+                    Some(transaction)
+                );
+
+                let row = cursor
+                    .get_one::<TransactionMask<TransactionSignedNoHash>>(
+                        (&transaction.hash()).into(),
+                    )
+                    .unwrap();
+                assert_eq!(row.map(|tx| tx.hash()), Some(transaction.hash()));
+                assert_eq!(cursor.number(), num + 1);
+            }
+
+            for _ in 0..10 {
+                let hash = B256::random();
+                assert_eq!(jar.transaction_id(hash).unwrap(), None);
+                assert_eq!(jar.transaction_by_hash(hash).unwrap(), None);
+
+                let number = cursor.number();
+                let row = cursor
+                    .get_one::<TransactionMask<TransactionSignedNoHash>>((&hash).into())
+                    .unwrap();
+                assert!(
+                    (row.is_none() && cursor.number() == number) ||
+                        row.is_some_and(|row| row.hash() != hash)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn cursor_parts_reuse() {
+        let snap_file = tempfile::NamedTempFile::new().unwrap();
+        let headers = create_headers_jar(snap_file.path(), 0..=99);
 
-    start..end
+        let manager = SnapshotProvider::default();
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Headers, 0, Some(snap_file.path().into()))
+            .unwrap();
+        assert!(jar.cursor_parts.lock().is_none());
+
+        // Sequential queries go through the same buffer, which isn't allocated again.
+        let buffer = || jar.cursor_parts.lock().as_ref().map(|(_, buffer)| buffer.as_ptr());
+        jar.header_by_number(0).unwrap();
+        let first_buffer = buffer();
+        assert!(first_buffer.is_some());
+        for header in &headers {
+            assert_eq!(jar.header_by_number(header.number).unwrap(), Some(header.header.clone()));
+            assert_eq!(jar.block_hash(header.number).unwrap(), Some(header.hash()));
+            assert_eq!(buffer(), first_buffer);
+        }
+
+        // Cursors alive at the same time don't share it.
+        let mut first = jar.cursor().unwrap();
+        assert!(jar.cursor_parts.lock().is_none());
+        let mut second = jar.cursor().unwrap();
+        assert_eq!(
+            first.get_one::<HeaderMask<BlockHash>>(10u64.into()).unwrap(),
+            Some(headers[10].hash())
+        );
+        assert_eq!(
+            second.get_one::<HeaderMask<BlockHash>>(20u64.into()).unwrap(),
+            Some(headers[20].hash())
+        );
+        assert_eq!(
+            first.get_one::<HeaderMask<BlockHash>>(11u64.into()).unwrap(),
+            Some(headers[11].hash())
+        );
+
+        drop(first);
+        assert!(jar.cursor_parts.lock().is_some());
+        drop(second);
+        assert_eq!(jar.header_by_number(50).unwrap(), Some(headers[50].header.clone()));
+    }
+
+    #[test]
+    fn contains_bounds() {
+        let snap_file = tempfile::NamedTempFile::new().unwrap();
+        create_jar(
+            snap_file.path(),
+            SnapshotSegment::Receipts,
+            10..=19,
+            100..=149,
+            vec![vec![vec![0]; 50]],
+            vec![],
+        );
+
+        let manager = SnapshotProvider::default();
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Receipts, 10, Some(snap_file.path().into()))
+            .unwrap();
+
+        // Lower bound, just inside of it, upper bound, just inside of it and just outside of both.
+        for num in [10, 11, 18, 19] {
+            assert!(jar.contains_block(num));
+        }
+        for num in [9, 20] {
+            assert!(!jar.contains_block(num));
+        }
+
+        for num in [100, 101, 148, 149] {
+            assert!(jar.contains_tx(num));
+        }
+        for num in [99, 150] {
+            assert!(!jar.contains_tx(num));
+        }
+    }
+
+    #[test]
+    fn raw_transactions_and_receipts() {
+        let (transactions_file, receipts_file) =
+            (tempfile::NamedTempFile::new().unwrap(), tempfile::NamedTempFile::new().unwrap());
+        let transactions = create_transactions_jar(transactions_file.path(), 0..=9);
+        let receipts = create_receipts_jar(receipts_file.path(), 0..=9);
+
+        let manager = SnapshotProvider::default();
+        load_jars(
+            &manager,
+            &[
+                (SnapshotSegment::Transactions, 0, transactions_file.path()),
+                (SnapshotSegment::Receipts, 0, receipts_file.path()),
+            ],
+        );
+        let transactions_jar =
+            manager.get_segment_provider(SnapshotSegment::Transactions, 0, None).unwrap();
+        let receipts_jar =
+            manager.get_segment_provider(SnapshotSegment::Receipts, 0, None).unwrap();
+
+        for num in 0..10 {
+            let raw = transactions_jar.raw_transaction_by_id(num).unwrap().unwrap();
+            let transaction = TransactionSignedNoHash::decompress(raw).unwrap();
+            assert_eq!(
+                Some(&transaction),
+                transactions_jar.transaction_by_id_no_hash(num).unwrap().as_ref()
+            );
+            assert_eq!(transaction.with_hash(), transactions[num as usize]);
+
+            let raw = receipts_jar.raw_receipt_by_id(num).unwrap().unwrap();
+            let receipt = Receipt::decompress(raw).unwrap();
+            assert_eq!(Some(&receipt), receipts_jar.receipt(num).unwrap().as_ref());
+            assert_eq!(receipt, receipts[num as usize]);
+        }
+
+        assert_matches!(
+            transactions_jar.raw_transaction_by_id(10),
+            Err(RethError::Provider(ProviderError::SnapshotOutOfRange { .. }))
+        );
+        assert_matches!(
+            receipts_jar.raw_receipt_by_id(10),
+            Err(RethError::Provider(ProviderError::SnapshotOutOfRange { .. }))
+        );
+    }
+
+    #[test]
+    fn headers_td_range_matches_separate_reads() {
+        let snap_file = tempfile::NamedTempFile::new().unwrap();
+        create_headers_jar(snap_file.path(), 0..=49);
+
+        let manager = SnapshotProvider::default();
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Headers, 0, Some(snap_file.path().into()))
+            .unwrap();
+
+        for range in [0..50, 10..20, 45..60] {
+            let expected = jar
+                .headers_range(range.clone())
+                .unwrap()
+                .into_iter()
+                .map(|header| {
+                    let td = jar.header_td_by_number(header.number).unwrap().unwrap();
+                    (header, td)
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(jar.headers_td_range(range).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn out_of_range_and_missing() {
+        let snap_file = tempfile::NamedTempFile::new().unwrap();
+        let headers = create_headers_jar(snap_file.path(), 10..=19);
+
+        // The jar covers more blocks than it holds, like one still being filled.
+        let partial_file = tempfile::NamedTempFile::new().unwrap();
+        create_jar(
+            partial_file.path(),
+            SnapshotSegment::Headers,
+            20..=29,
+            20..=29,
+            vec![
+                headers[..5].iter().map(|header| header.header.clone().compress()).collect(),
+                vec![CompactU256::from(U256::ZERO).compress(); 5],
+                headers[..5].iter().map(|header| header.hash().compress()).collect(),
+            ],
+            vec![],
+        );
+
+        // Both jars fall under the same manager key, so they're loaded by different managers.
+        let (manager, partial_manager) = (SnapshotProvider::default(), SnapshotProvider::default());
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Headers, 10, Some(snap_file.path().into()))
+            .unwrap();
+        let partial = partial_manager
+            .get_segment_provider(SnapshotSegment::Headers, 20, Some(partial_file.path().into()))
+            .unwrap();
+
+        // Outside of the covered range, another jar has to be asked.
+        for num in [9, 20] {
+            assert_matches!(
+                jar.header_by_number(num),
+                Err(RethError::Provider(ProviderError::SnapshotOutOfRange {
+                    segment: SnapshotSegment::Headers,
+                    requested,
+                    covered,
+                })) if requested == (num..=num) && covered == (10..=19)
+            );
+            assert_matches!(
+                jar.block_hash(num),
+                Err(RethError::Provider(ProviderError::SnapshotOutOfRange { .. }))
+            );
+            assert_matches!(
+                jar.sealed_header(num),
+                Err(RethError::Provider(ProviderError::SnapshotOutOfRange { .. }))
+            );
+        }
+        assert_matches!(
+            jar.headers_range(20..30),
+            Err(RethError::Provider(ProviderError::SnapshotOutOfRange { requested, .. }))
+                if requested == (20..=29)
+        );
+        assert_matches!(
+            jar.canonical_hashes_range(0, 10),
+            Err(RethError::Provider(ProviderError::SnapshotOutOfRange { .. }))
+        );
+
+        // Within it, missing rows are genuine misses.
+        assert_eq!(partial.header_by_number(24).unwrap(), Some(headers[4].header.clone()));
+        assert_eq!(partial.header_by_number(25).unwrap(), None);
+        assert_eq!(partial.block_hash(29).unwrap(), None);
+        assert_eq!(partial.headers_range(22..30).unwrap().len(), 3);
+        assert!(partial.headers_range(25..30).unwrap().is_empty());
+
+        // Ranges partially covered return their covered part, and empty ones are never out of it.
+        assert_eq!(jar.headers_range(15..25).unwrap().len(), 5);
+        assert!(jar.headers_range(30..30).unwrap().is_empty());
+    }
+
+    #[test]
+    fn senders_from_segment_or_recovered() {
+        let transactions_file = tempfile::NamedTempFile::new().unwrap();
+        let senders_file = tempfile::NamedTempFile::new().unwrap();
+        let transactions = create_transactions_jar(transactions_file.path(), 100..=149);
+        let senders = create_senders_jar(senders_file.path(), 100..=149, &transactions);
+
+        let manager = SnapshotProvider::default();
+        load_jars(
+            &manager,
+            &[
+                (SnapshotSegment::Transactions, 0, transactions_file.path()),
+                (SnapshotSegment::Senders, 0, senders_file.path()),
+            ],
+        );
+
+        // Recovered from the transactions.
+        let recovering =
+            manager.get_segment_provider(SnapshotSegment::Transactions, 0, None).unwrap();
+        // Read from the senders segment, which holds no transactions to recover them from.
+        let precomputed = manager.get_segment_provider(SnapshotSegment::Senders, 0, None).unwrap();
+        // Prefers the senders auxiliar over recovering.
+        let with_auxiliar = manager
+            .get_segment_provider(SnapshotSegment::Transactions, 0, None)
+            .unwrap()
+            .with_auxiliar(
+                manager.get_segment_provider(SnapshotSegment::Senders, 0, None).unwrap(),
+            );
+
+        for jar in [&recovering, &precomputed, &with_auxiliar] {
+            assert_eq!(jar.senders_by_tx_range(..).unwrap(), senders);
+            assert_eq!(jar.senders_by_tx_range(110..120).unwrap(), senders[10..20]);
+            assert_eq!(jar.senders_by_tx_range(140..200).unwrap(), senders[40..]);
+            for num in [100, 125, 149] {
+                assert_eq!(jar.transaction_sender(num).unwrap(), Some(senders[num as usize - 100]));
+            }
+            assert_matches!(
+                jar.transaction_sender(150),
+                Err(RethError::Provider(ProviderError::SnapshotOutOfRange { .. }))
+            );
+        }
+    }
+
+    #[test]
+    fn prefetch_range_is_best_effort() {
+        let snap_file = tempfile::NamedTempFile::new().unwrap();
+        let receipts = create_receipts_jar(snap_file.path(), 100..=149);
+
+        let manager = SnapshotProvider::default();
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Receipts, 0, Some(snap_file.path().into()))
+            .unwrap();
+
+        // Hints never fail, even for ranges outside of the jar, and don't change what's read.
+        for range in [100..150, 120..130, 140..200, 0..100, 200..300, 120..120] {
+            jar.prefetch_range(range).unwrap();
+        }
+        jar.prefetch_range(..).unwrap();
+        assert_eq!(jar.receipts_by_tx_range(..).unwrap(), receipts);
+    }
+
+    #[test]
+    fn headers_iter_is_lazy() {
+        let snap_file = tempfile::NamedTempFile::new().unwrap();
+        let headers = create_headers_jar(snap_file.path(), 10..=19);
+
+        let manager = SnapshotProvider::default();
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Headers, 10, Some(snap_file.path().into()))
+            .unwrap();
+
+        // Searching stops at the first match, leaving the headers after it unread.
+        let target = headers[3].nonce;
+        let mut iter = jar.headers_iter(..).unwrap();
+        let found = iter.by_ref().find(|header| header.as_ref().unwrap().nonce == target);
+        assert_eq!(found.unwrap().unwrap(), headers[3].header);
+        assert_eq!(iter.next().unwrap().unwrap(), headers[4].header);
+        drop(iter);
+
+        let mut iter = jar.sealed_headers_iter(15..).unwrap();
+        assert_eq!(iter.next().unwrap().unwrap(), headers[5]);
+        drop(iter);
+
+        // They end at the first missing header, like the allocating forms.
+        assert_eq!(jar.headers_iter(15..25).unwrap().count(), 5);
+        assert_eq!(
+            jar.sealed_headers_iter(12..30).unwrap().collect::<RethResult<Vec<_>>>().unwrap(),
+            jar.sealed_headers_range(12..30).unwrap()
+        );
+
+        // Cut into the total difficulty and hash columns of the last block, so that reading it
+        // would go out of bounds.
+        let truncated_file = tempfile::NamedTempFile::new().unwrap();
+        create_headers_jar_from(truncated_file.path(), headers.clone());
+        let file = std::fs::OpenOptions::new().write(true).open(truncated_file.path()).unwrap();
+        file.set_len(file.metadata().unwrap().len() - 40).unwrap();
+
+        let manager = SnapshotProvider::default();
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Headers, 10, Some(truncated_file.path().into()))
+            .unwrap();
+        assert_matches!(
+            jar.verify(),
+            Err(RethError::Provider(ProviderError::CorruptedSnapshot { number: 19, .. }))
+        );
+
+        // Consuming the headers up to the one before it leaves it unread.
+        let found = jar
+            .headers_iter(..)
+            .unwrap()
+            .find(|header| header.as_ref().unwrap().number == 18)
+            .unwrap();
+        assert_eq!(found.unwrap(), headers[8].header);
+        let iterated = jar.sealed_headers_iter(15..).unwrap().take(4);
+        assert_eq!(iterated.collect::<RethResult<Vec<_>>>().unwrap(), headers[5..9]);
+    }
+
+    #[test]
+    fn verify_jars() {
+        let headers_file = tempfile::NamedTempFile::new().unwrap();
+        let transactions_file = tempfile::NamedTempFile::new().unwrap();
+        let receipts_file = tempfile::NamedTempFile::new().unwrap();
+        let bodies_file = tempfile::NamedTempFile::new().unwrap();
+        let senders_file = tempfile::NamedTempFile::new().unwrap();
+        create_headers_jar(headers_file.path(), 10..=19);
+        let transactions = create_transactions_jar(transactions_file.path(), 100..=109);
+        create_receipts_jar(receipts_file.path(), 100..=109);
+        create_block_body_indices_jar(bodies_file.path(), 10, 100, &[3, 0, 7]);
+        create_senders_jar(senders_file.path(), 100..=109, &transactions);
+
+        let manager = SnapshotProvider::default();
+        for (segment, block, path) in [
+            (SnapshotSegment::Headers, 10, headers_file.path()),
+            (SnapshotSegment::Transactions, 0, transactions_file.path()),
+            (SnapshotSegment::Receipts, 0, receipts_file.path()),
+            (SnapshotSegment::BlockBodyIndices, 10, bodies_file.path()),
+            (SnapshotSegment::Senders, 0, senders_file.path()),
+        ] {
+            manager
+                .get_segment_provider(segment, block, Some(path.into()))
+                .unwrap()
+                .verify()
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn verify_reports_corrupted_row() {
+        let snap_file = tempfile::NamedTempFile::new().unwrap();
+        let headers = random_header_range(&mut generators::rng(), 10..20, B256::random());
+
+        // Block 13 is stored with a hash other than its own.
+        let mut columns = vec![vec![], vec![], vec![]];
+        for header in &headers {
+            let hash = if header.number == 13 { B256::random() } else { header.hash() };
+            columns[0].push(header.header.clone().compress());
+            columns[1].push(CompactU256::from(header.difficulty).compress());
+            columns[2].push(hash.compress());
+        }
+        create_jar(snap_file.path(), SnapshotSegment::Headers, 10..=19, 10..=19, columns, vec![]);
+
+        let manager = SnapshotProvider::default();
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Headers, 10, Some(snap_file.path().into()))
+            .unwrap();
+        assert_matches!(
+            jar.verify(),
+            Err(RethError::Provider(ProviderError::CorruptedSnapshot { number: 13, .. }))
+        );
+    }
+
+    #[test]
+    fn verify_reports_truncated_data() {
+        let snap_file = tempfile::NamedTempFile::new().unwrap();
+        create_headers_jar(snap_file.path(), 10..=19);
+
+        // Cut into the total difficulty and hash columns of the last block.
+        let file = std::fs::OpenOptions::new().write(true).open(snap_file.path()).unwrap();
+        file.set_len(file.metadata().unwrap().len() - 40).unwrap();
+
+        let manager = SnapshotProvider::default();
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Headers, 10, Some(snap_file.path().into()))
+            .unwrap();
+        assert_matches!(
+            jar.verify(),
+            Err(RethError::Provider(ProviderError::CorruptedSnapshot { number: 19, .. }))
+        );
+    }
+
+    #[test]
+    fn sealed_header_by_hash_uses_stored_hash() {
+        let snap_file = tempfile::NamedTempFile::new().unwrap();
+        let headers = create_headers_jar(snap_file.path(), 10..=19);
+
+        let manager = SnapshotProvider::default();
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Headers, 10, Some(snap_file.path().into()))
+            .unwrap();
+
+        for header in &headers {
+            let hash = header.hash();
+            let sealed = jar.sealed_header_by_hash(&hash).unwrap();
+            assert_eq!(sealed, jar.header(&hash).unwrap().map(|header| header.seal(hash)));
+            assert_eq!(sealed.as_ref(), Some(header));
+        }
+        assert_eq!(jar.sealed_header_by_hash(&B256::random()).unwrap(), None);
+
+        // A jar storing a hash other than the header's own shows it's used as is, not recomputed.
+        let other_file = tempfile::NamedTempFile::new().unwrap();
+        let stored_hash = B256::random();
+        let header = headers[0].header.clone();
+        create_jar(
+            other_file.path(),
+            SnapshotSegment::Headers,
+            0..=0,
+            0..=0,
+            vec![
+                vec![header.clone().compress()],
+                vec![CompactU256::from(header.difficulty).compress()],
+                vec![stored_hash.compress()],
+            ],
+            vec![stored_hash],
+        );
+
+        let manager = SnapshotProvider::default();
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Headers, 0, Some(other_file.path().into()))
+            .unwrap();
+        let sealed = jar.sealed_header_by_hash(&stored_hash).unwrap().unwrap();
+        assert_eq!(sealed.hash(), stored_hash);
+        assert_ne!(sealed.hash(), header.hash_slow());
+    }
+
+    #[test]
+    fn range_methods_output() {
+        let headers_file = tempfile::NamedTempFile::new().unwrap();
+        let transactions_file = tempfile::NamedTempFile::new().unwrap();
+        let receipts_file = tempfile::NamedTempFile::new().unwrap();
+        let senders_file = tempfile::NamedTempFile::new().unwrap();
+        let headers = create_headers_jar(headers_file.path(), 10..=19);
+        let transactions = create_transactions_jar(transactions_file.path(), 100..=109);
+        let receipts = create_receipts_jar(receipts_file.path(), 100..=109);
+        let senders = create_senders_jar(senders_file.path(), 100..=109, &transactions);
+        let transactions =
+            transactions.into_iter().map(TransactionSignedNoHash::from).collect::<Vec<_>>();
+
+        let manager = SnapshotProvider::default();
+        load_jars(
+            &manager,
+            &[
+                (SnapshotSegment::Headers, 10, headers_file.path()),
+                (SnapshotSegment::Transactions, 0, transactions_file.path()),
+                (SnapshotSegment::Receipts, 0, receipts_file.path()),
+            ],
+        );
+        let headers_jar = manager.get_segment_provider(SnapshotSegment::Headers, 10, None).unwrap();
+        let transactions_jar =
+            manager.get_segment_provider(SnapshotSegment::Transactions, 0, None).unwrap();
+        let receipts_jar =
+            manager.get_segment_provider(SnapshotSegment::Receipts, 0, None).unwrap();
+
+        let senders_manager = SnapshotProvider::default();
+        let senders_jar = senders_manager
+            .get_segment_provider(SnapshotSegment::Senders, 0, Some(senders_file.path().into()))
+            .unwrap();
+
+        // Ranges fully inside, overflowing the end, and empty.
+        for (start, end) in [(0, 10), (3, 7), (5, 20), (4, 4)] {
+            let blocks = 10 + start..10 + end;
+            let txs = 100 + start..100 + end;
+            let (start, end) = (start as usize, end.min(10) as usize);
+            let sealed = &headers[start..end];
+            let unsealed = sealed.iter().map(|header| header.header.clone()).collect::<Vec<_>>();
+
+            assert_eq!(headers_jar.headers_range(blocks.clone()).unwrap(), unsealed);
+            assert_eq!(headers_jar.sealed_headers_range(blocks.clone()).unwrap(), sealed);
+            assert_eq!(
+                headers_jar.canonical_hashes_range(blocks.start, blocks.end).unwrap(),
+                sealed.iter().map(|header| header.hash()).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                headers_jar.headers_range_rev(blocks.clone()).unwrap(),
+                unsealed.iter().rev().cloned().collect::<Vec<_>>()
+            );
+            assert_eq!(
+                headers_jar.map_headers_range(blocks.clone(), |num, _| num).unwrap(),
+                sealed.iter().map(|header| header.number).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                headers_jar
+                    .headers_td_range(blocks.clone())
+                    .unwrap()
+                    .into_iter()
+                    .map(|(header, _)| header)
+                    .collect::<Vec<_>>(),
+                unsealed
+            );
+
+            assert_eq!(
+                transactions_jar.transactions_by_tx_range(txs.clone()).unwrap(),
+                &transactions[start..end]
+            );
+            assert_eq!(
+                transactions_jar.transactions_by_tx_range_rev(txs.clone()).unwrap(),
+                transactions[start..end].iter().rev().cloned().collect::<Vec<_>>()
+            );
+            assert_eq!(
+                transactions_jar
+                    .transactions_from(txs.start, (txs.end - txs.start) as usize)
+                    .unwrap(),
+                (txs.start..).zip(transactions[start..end].iter().cloned()).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                receipts_jar.receipts_by_tx_range(txs.clone()).unwrap(),
+                &receipts[start..end]
+            );
+            assert_eq!(senders_jar.senders_by_tx_range(txs).unwrap(), &senders[start..end]);
+        }
+    }
+
+    #[test]
+    fn canonical_hashes_strided() {
+        let snap_file = tempfile::NamedTempFile::new().unwrap();
+        let headers = create_headers_jar(snap_file.path(), 10..=39);
+        let hashes = |numbers: &[u64]| {
+            numbers.iter().map(|number| headers[*number as usize - 10].hash()).collect::<Vec<_>>()
+        };
+
+        let manager = SnapshotProvider::default();
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Headers, 10, Some(snap_file.path().into()))
+            .unwrap();
+
+        assert_eq!(jar.canonical_hashes_strided(10, 40, 10, false).unwrap(), hashes(&[10, 20, 30]));
+        assert_eq!(jar.canonical_hashes_strided(10, 40, 10, true).unwrap(), hashes(&[39, 29, 19]));
+        assert_eq!(jar.canonical_hashes_strided(12, 20, 3, false).unwrap(), hashes(&[12, 15, 18]));
+        assert_eq!(jar.canonical_hashes_strided(12, 20, 3, true).unwrap(), hashes(&[19, 16, 13]));
+        assert_eq!(jar.canonical_hashes_strided(15, 16, 7, true).unwrap(), hashes(&[15]));
+
+        // Samples stay aligned to the requested range where it goes past the jar.
+        assert_eq!(jar.canonical_hashes_strided(4, 40, 10, false).unwrap(), hashes(&[14, 24, 34]));
+        assert_eq!(jar.canonical_hashes_strided(10, 45, 10, true).unwrap(), hashes(&[34, 24, 14]));
+        assert_eq!(jar.canonical_hashes_strided(0, 15, 20, false).unwrap(), hashes(&[]));
+        assert_eq!(jar.canonical_hashes_strided(35, 60, 20, true).unwrap(), hashes(&[39]));
+        assert_eq!(jar.canonical_hashes_strided(35, 60, 30, true).unwrap(), hashes(&[]));
+
+        // A step of one is the same as reading the whole range.
+        for descending in [false, true] {
+            let mut expected = jar.canonical_hashes_range(10, 40).unwrap();
+            if descending {
+                expected.reverse();
+            }
+            assert_eq!(jar.canonical_hashes_strided(10, 40, 1, descending).unwrap(), expected);
+        }
+
+        // Backwards and empty ranges have nothing to sample, in either direction.
+        for descending in [false, true] {
+            assert_eq!(jar.canonical_hashes_strided(30, 20, 2, descending).unwrap(), hashes(&[]));
+            assert_eq!(jar.canonical_hashes_strided(20, 20, 2, descending).unwrap(), hashes(&[]));
+            assert_matches!(
+                jar.canonical_hashes_strided(10, 40, 0, descending),
+                Err(RethError::Provider(ProviderError::ZeroRangeStep))
+            );
+        }
+    }
+
+    #[test]
+    fn get_many_matches_get_one() {
+        let snap_file = tempfile::NamedTempFile::new().unwrap();
+        let transactions = create_transactions_jar(snap_file.path(), 100..=149);
+
+        let manager = SnapshotProvider::default();
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Transactions, 0, Some(snap_file.path().into()))
+            .unwrap();
+        let mut cursor = jar.cursor().unwrap();
+
+        // Ascending, unordered, repeated, and partially or fully outside of the jar.
+        for numbers in [
+            (100..150).collect::<Vec<u64>>(),
+            vec![140, 101, 120, 100, 149],
+            vec![110, 110, 105, 110],
+            vec![0, 99, 100, 150, 149, 1000],
+            vec![200, 300],
+            vec![],
+        ] {
+            let expected = numbers
+                .iter()
+                .map(|num| {
+                    cursor
+                        .get_one::<TransactionMask<TransactionSignedNoHash>>((*num).into())
+                        .unwrap()
+                })
+                .collect::<Vec<_>>();
+            let many =
+                cursor.get_many::<TransactionMask<TransactionSignedNoHash>>(&numbers).unwrap();
+            assert_eq!(many, expected);
+        }
+
+        // Numbers before the jar are skipped, and it stops at the first one after it.
+        let mut read = Vec::new();
+        let count = cursor
+            .get_many_with::<TransactionMask<TransactionSignedNoHash>>(
+                [50, 99, 148, 149, 150, 151],
+                |num, tx| {
+                    read.push((num, tx));
+                    Ok(())
+                },
+            )
+            .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(
+            read,
+            [148u64, 149]
+                .map(|num| (num, transactions[num as usize - 100].clone().into()))
+                .to_vec()
+        );
+
+        assert_eq!(
+            jar.transactions_by_tx_range(..).unwrap(),
+            transactions.into_iter().map(TransactionSignedNoHash::from).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            jar.transactions_by_tx_range(120..170).unwrap(),
+            jar.transactions_iter(120..170).unwrap().collect::<RethResult<Vec<_>>>().unwrap()
+        );
+    }
+
+    #[test]
+    fn transaction_meta_by_id_configurations() {
+        let headers_file = tempfile::NamedTempFile::new().unwrap();
+        let transactions_file = tempfile::NamedTempFile::new().unwrap();
+        let bodies_file = tempfile::NamedTempFile::new().unwrap();
+        let headers = create_headers_jar(headers_file.path(), 0..=3);
+        let transactions = create_transactions_jar(transactions_file.path(), 0..=5);
+        create_block_body_indices_jar(bodies_file.path(), 0, 0, &[2, 0, 3, 1]);
+
+        let manager = SnapshotProvider::default();
+        load_jars(
+            &manager,
+            &[
+                (SnapshotSegment::Headers, 0, headers_file.path()),
+                (SnapshotSegment::Transactions, 0, transactions_file.path()),
+                (SnapshotSegment::BlockBodyIndices, 0, bodies_file.path()),
+            ],
+        );
+        let bodies = || manager.get_segment_provider(SnapshotSegment::BlockBodyIndices, 0, None);
+
+        // Second transaction of block 2.
+        let partial = TransactionMeta {
+            tx_hash: transactions[3].hash(),
+            index: 1,
+            block_number: 2,
+            ..Default::default()
+        };
+
+        // Only the block body indices.
+        let index_only = manager
+            .get_segment_provider(SnapshotSegment::Transactions, 0, None)
+            .unwrap()
+            .with_auxiliar(bodies().unwrap());
+        assert_eq!(index_only.transaction_meta_by_id(3).unwrap(), Some(partial));
+        assert_eq!(index_only.transaction_meta_by_id(6).unwrap(), None);
+
+        // With the headers too, it's the same as the one found by hash.
+        let with_headers = manager
+            .get_segment_provider(SnapshotSegment::Headers, 0, None)
+            .unwrap()
+            .with_auxiliar(
+                manager.get_segment_provider(SnapshotSegment::Transactions, 0, None).unwrap(),
+            )
+            .with_auxiliar(bodies().unwrap());
+        let meta = with_headers.transaction_meta_by_id(3).unwrap().unwrap();
+        assert_eq!(
+            meta,
+            TransactionMeta {
+                block_hash: headers[2].hash(),
+                base_fee: headers[2].base_fee_per_gas,
+                excess_blob_gas: headers[2].excess_blob_gas,
+                ..partial
+            }
+        );
+        assert_eq!(
+            Some(meta),
+            with_headers
+                .transaction_by_hash_with_meta(transactions[3].hash())
+                .unwrap()
+                .map(|(_, meta)| meta)
+        );
+    }
+
+    #[test]
+    fn row_counts() {
+        let headers_file = tempfile::NamedTempFile::new().unwrap();
+        let transactions_file = tempfile::NamedTempFile::new().unwrap();
+        let receipts_file = tempfile::NamedTempFile::new().unwrap();
+        create_headers_jar(headers_file.path(), 10..=19);
+        create_transactions_jar(transactions_file.path(), 100..=149);
+        create_receipts_jar(receipts_file.path(), 100..=129);
+
+        let manager = SnapshotProvider::default();
+        load_jars(
+            &manager,
+            &[
+                (SnapshotSegment::Headers, 10, headers_file.path()),
+                (SnapshotSegment::Transactions, 0, transactions_file.path()),
+                (SnapshotSegment::Receipts, 0, receipts_file.path()),
+            ],
+        );
+        let headers = manager.get_segment_provider(SnapshotSegment::Headers, 10, None).unwrap();
+        let transactions =
+            manager.get_segment_provider(SnapshotSegment::Transactions, 0, None).unwrap();
+        let receipts = manager.get_segment_provider(SnapshotSegment::Receipts, 0, None).unwrap();
+
+        let range_len = |range: Range<u64>| range.end - range.start;
+        assert_eq!(headers.num_headers(), range_len(headers.block_range()));
+        assert_eq!(transactions.num_transactions(), range_len(transactions.tx_range()));
+        assert_eq!(receipts.num_receipts(), range_len(receipts.tx_range()));
+
+        // Other segments hold none of them.
+        for jar in [&headers, &transactions, &receipts] {
+            let segment = jar.user_header().segment();
+            assert_eq!(jar.num_headers() == 0, segment != SnapshotSegment::Headers);
+            assert_eq!(jar.num_transactions() == 0, segment != SnapshotSegment::Transactions);
+            assert_eq!(jar.num_receipts() == 0, segment != SnapshotSegment::Receipts);
+        }
+    }
+
+    #[test]
+    fn range_filters() {
+        let transactions_file = tempfile::NamedTempFile::new().unwrap();
+        let receipts_file = tempfile::NamedTempFile::new().unwrap();
+        let transactions = create_transactions_jar(transactions_file.path(), 100..=149);
+        let receipts = create_receipts_jar(receipts_file.path(), 100..=149);
+
+        let manager = SnapshotProvider::default();
+        load_jars(
+            &manager,
+            &[
+                (SnapshotSegment::Transactions, 0, transactions_file.path()),
+                (SnapshotSegment::Receipts, 0, receipts_file.path()),
+            ],
+        );
+        let transactions_jar =
+            manager.get_segment_provider(SnapshotSegment::Transactions, 0, None).unwrap();
+        let receipts_jar =
+            manager.get_segment_provider(SnapshotSegment::Receipts, 0, None).unwrap();
+
+        // Nothing matches, even though the whole range is scanned.
+        assert!(transactions_jar
+            .transactions_by_tx_range_filter(.., |_| false)
+            .unwrap()
+            .is_empty());
+        assert!(receipts_jar.receipts_by_tx_range_filter(.., |_| false).unwrap().is_empty());
+
+        // Everything matches.
+        assert_eq!(
+            transactions_jar.transactions_by_tx_range_filter(.., |_| true).unwrap(),
+            transactions_jar.transactions_by_tx_range(..).unwrap()
+        );
+        assert_eq!(receipts_jar.receipts_by_tx_range_filter(.., |_| true).unwrap(), receipts);
+
+        // Only the matching ones are returned, in order, from the requested range.
+        let nonce = transactions[120].nonce();
+        assert_eq!(
+            transactions_jar
+                .transactions_by_tx_range_filter(110..140, |tx| tx.nonce() <= nonce)
+                .unwrap(),
+            transactions[10..40]
+                .iter()
+                .filter(|tx| tx.nonce() <= nonce)
+                .cloned()
+                .map(TransactionSignedNoHash::from)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            receipts_jar.receipts_by_tx_range_filter(110..140, |receipt| receipt.success).unwrap(),
+            receipts[10..40].iter().filter(|receipt| receipt.success).cloned().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn receipts_matching_filter() {
+        let headers_file = tempfile::NamedTempFile::new().unwrap();
+        let receipts_file = tempfile::NamedTempFile::new().unwrap();
+        let bodies_file = tempfile::NamedTempFile::new().unwrap();
+
+        let (address, other_address) = (Address::random(), Address::random());
+        let (topic, other_topic) = (B256::random(), B256::random());
+        let log = |address, topics| Log { address, topics, data: Bytes::default() };
+
+        // A single transaction per block.
+        let receipts = [
+            // Matches the filter of `address` and `topic`.
+            vec![log(address, vec![topic])],
+            // Matches it through the bloom, but no single log matches it.
+            vec![log(address, vec![other_topic]), log(other_address, vec![topic])],
+            // Ruled out by the bloom.
+            vec![log(other_address, vec![other_topic])],
+        ]
+        .map(|logs| Receipt { logs, ..Default::default() });
+
+        let headers = random_header_range(&mut generators::rng(), 0..3, B256::random())
+            .into_iter()
+            .zip(&receipts)
+            .map(|(header, receipt)| {
+                let mut header = header.unseal();
+                header.logs_bloom = receipt.bloom_slow();
+                header.seal_slow()
+            })
+            .collect::<Vec<_>>();
+        create_jar(
+            headers_file.path(),
+            SnapshotSegment::Headers,
+            0..=2,
+            0..=2,
+            vec![
+                headers.iter().map(|header| header.header.clone().compress()).collect(),
+                headers
+                    .iter()
+                    .map(|header| CompactU256::from(header.difficulty).compress())
+                    .collect(),
+                headers.iter().map(|header| header.hash().compress()).collect(),
+            ],
+            headers.iter().map(|header| header.hash()).collect(),
+        );
+        create_jar(
+            receipts_file.path(),
+            SnapshotSegment::Receipts,
+            0..=2,
+            0..=2,
+            vec![receipts.iter().map(|receipt| receipt.clone().compress()).collect()],
+            vec![],
+        );
+        create_block_body_indices_jar(bodies_file.path(), 0, 0, &[1, 1, 1]);
+
+        let manager = SnapshotProvider::default();
+        load_jars(
+            &manager,
+            &[
+                (SnapshotSegment::Headers, 0, headers_file.path()),
+                (SnapshotSegment::Receipts, 0, receipts_file.path()),
+                (SnapshotSegment::BlockBodyIndices, 0, bodies_file.path()),
+            ],
+        );
+        let receipts_jar = || manager.get_segment_provider(SnapshotSegment::Receipts, 0, None);
+        let with_blooms = receipts_jar()
+            .unwrap()
+            .with_auxiliar(manager.get_segment_provider(SnapshotSegment::Headers, 0, None).unwrap())
+            .with_auxiliar(
+                manager.get_segment_provider(SnapshotSegment::BlockBodyIndices, 0, None).unwrap(),
+            );
+
+        // Blooms only skip receipts, so the results are the same with and without them.
+        for jar in [receipts_jar().unwrap(), with_blooms] {
+            let matching = |addresses: &[Address], topics: &[Vec<B256>]| {
+                jar.receipts_matching_range(.., addresses, topics)
+                    .unwrap()
+                    .into_iter()
+                    .map(|(num, receipt)| {
+                        assert_eq!(receipt, receipts[num as usize]);
+                        num
+                    })
+                    .collect::<Vec<_>>()
+            };
+
+            // Matching the bloom of the second block is not enough.
+            assert_eq!(matching(&[address], &[vec![topic]]), vec![0]);
+            // Anything matches an empty filter.
+            assert_eq!(matching(&[], &[]), vec![0, 1, 2]);
+            assert_eq!(matching(&[], &[vec![]]), vec![0, 1, 2]);
+            // Any address or topic of a position, but all positions.
+            assert_eq!(matching(&[address, other_address], &[]), vec![0, 1, 2]);
+            assert_eq!(matching(&[address], &[vec![topic, other_topic]]), vec![0, 1]);
+            assert_eq!(matching(&[other_address], &[vec![topic]]), vec![1]);
+            assert_eq!(matching(&[], &[vec![], vec![topic]]), Vec::<u64>::new());
+            assert_eq!(matching(&[Address::random()], &[]), Vec::<u64>::new());
+
+            assert_eq!(
+                jar.receipts_with_log_address_by_tx_range(address, ..)
+                    .unwrap()
+                    .into_iter()
+                    .map(|(num, _)| num)
+                    .collect::<Vec<_>>(),
+                vec![0, 1]
+            );
+            // Ranges starting after the first block start walking the blocks from theirs.
+            assert_eq!(
+                jar.receipts_with_log_address_by_tx_range(address, 1..)
+                    .unwrap()
+                    .into_iter()
+                    .map(|(num, _)| num)
+                    .collect::<Vec<_>>(),
+                vec![1]
+            );
+        }
+    }
+
+    #[test]
+    fn headers_by_hashes_order() {
+        let snap_file = tempfile::NamedTempFile::new().unwrap();
+        let headers = create_headers_jar(snap_file.path(), 10..=19);
+
+        let manager = SnapshotProvider::default();
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Headers, 10, Some(snap_file.path().into()))
+            .unwrap();
+
+        let hashes = [
+            headers[7].hash(),
+            B256::random(),
+            headers[0].hash(),
+            headers[7].hash(),
+            B256::random(),
+            headers[9].hash(),
+        ];
+        assert_eq!(
+            jar.headers_by_hashes(&hashes).unwrap(),
+            vec![
+                Some(headers[7].header.clone()),
+                None,
+                Some(headers[0].header.clone()),
+                Some(headers[7].header.clone()),
+                None,
+                Some(headers[9].header.clone()),
+            ]
+        );
+        assert_eq!(
+            jar.headers_by_hashes(&hashes).unwrap(),
+            hashes.iter().map(|hash| jar.header(hash).unwrap()).collect::<Vec<_>>()
+        );
+        assert_eq!(jar.headers_by_hashes(&[]).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn transactions_by_block_hash_or_number() {
+        let headers_file = tempfile::NamedTempFile::new().unwrap();
+        let transactions_file = tempfile::NamedTempFile::new().unwrap();
+        let bodies_file = tempfile::NamedTempFile::new().unwrap();
+        let headers = create_headers_jar(headers_file.path(), 0..=3);
+        let transactions = create_transactions_jar(transactions_file.path(), 0..=4);
+        create_block_body_indices_jar(bodies_file.path(), 0, 0, &[0, 2, 0, 3]);
+
+        let manager = SnapshotProvider::default();
+        load_jars(
+            &manager,
+            &[
+                (SnapshotSegment::Headers, 0, headers_file.path()),
+                (SnapshotSegment::Transactions, 0, transactions_file.path()),
+                (SnapshotSegment::BlockBodyIndices, 0, bodies_file.path()),
+            ],
+        );
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Headers, 0, None)
+            .unwrap()
+            .with_auxiliar(
+                manager.get_segment_provider(SnapshotSegment::Transactions, 0, None).unwrap(),
+            )
+            .with_auxiliar(
+                manager.get_segment_provider(SnapshotSegment::BlockBodyIndices, 0, None).unwrap(),
+            );
+
+        let expected = [vec![], transactions[..2].to_vec(), vec![], transactions[2..].to_vec()];
+        for (header, expected) in headers.iter().zip(expected) {
+            let by_number = jar.transactions_by_block(header.number.into()).unwrap();
+            assert_eq!(by_number, Some(expected));
+            assert_eq!(jar.transactions_by_block(header.hash().into()).unwrap(), by_number);
+        }
+
+        // Hashes not in the headers jar are not found.
+        assert_eq!(jar.transactions_by_block(B256::random().into()).unwrap(), None);
+    }
+
+    #[test]
+    fn receipt_by_hash_across_jars() {
+        let first_receipts_file = tempfile::NamedTempFile::new().unwrap();
+        let second_receipts_file = tempfile::NamedTempFile::new().unwrap();
+        let transactions_file = tempfile::NamedTempFile::new().unwrap();
+        let first_receipts = create_receipts_jar(first_receipts_file.path(), 0..=2);
+        let second_receipts = create_receipts_jar(second_receipts_file.path(), 3..=5);
+        let transactions = create_transactions_jar(transactions_file.path(), 0..=7);
+
+        // Both receipts jars would share the same manager key.
+        let manager = SnapshotProvider::default();
+        let second_manager = SnapshotProvider::default();
+        load_jars(
+            &manager,
+            &[
+                (SnapshotSegment::Receipts, 0, first_receipts_file.path()),
+                (SnapshotSegment::Transactions, 0, transactions_file.path()),
+            ],
+        );
+        load_jars(&second_manager, &[(SnapshotSegment::Receipts, 0, second_receipts_file.path())]);
+
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Receipts, 0, None)
+            .unwrap()
+            .with_auxiliar(
+                manager.get_segment_provider(SnapshotSegment::Transactions, 0, None).unwrap(),
+            )
+            .with_auxiliar(
+                second_manager.get_segment_provider(SnapshotSegment::Receipts, 0, None).unwrap(),
+            );
+
+        // Each receipt is read from the jar covering its transaction.
+        for (transaction, receipt) in
+            transactions.iter().zip(first_receipts.iter().chain(&second_receipts))
+        {
+            assert_eq!(jar.receipt_by_hash(transaction.hash()).unwrap().as_ref(), Some(receipt));
+        }
+
+        // Transactions without a receipts jar covering them, and unknown ones, are not found.
+        assert_eq!(jar.receipt_by_hash(transactions[6].hash()).unwrap(), None);
+        assert_eq!(jar.receipt_by_hash(B256::random()).unwrap(), None);
+    }
+
+    #[test]
+    fn empty_and_inverted_ranges() {
+        let headers_file = tempfile::NamedTempFile::new().unwrap();
+        let transactions_file = tempfile::NamedTempFile::new().unwrap();
+        let receipts_file = tempfile::NamedTempFile::new().unwrap();
+        let bodies_file = tempfile::NamedTempFile::new().unwrap();
+        create_headers_jar(headers_file.path(), 10..=19);
+        create_transactions_jar(transactions_file.path(), 100..=109);
+        create_receipts_jar(receipts_file.path(), 100..=109);
+        create_block_body_indices_jar(bodies_file.path(), 10, 100, &[4, 6]);
+
+        let manager = SnapshotProvider::default();
+        load_jars(
+            &manager,
+            &[
+                (SnapshotSegment::Headers, 10, headers_file.path()),
+                (SnapshotSegment::Transactions, 0, transactions_file.path()),
+                (SnapshotSegment::Receipts, 0, receipts_file.path()),
+                (SnapshotSegment::BlockBodyIndices, 10, bodies_file.path()),
+            ],
+        );
+        let headers = manager.get_segment_provider(SnapshotSegment::Headers, 10, None).unwrap();
+        let receipts = manager.get_segment_provider(SnapshotSegment::Receipts, 0, None).unwrap();
+        let transactions = manager
+            .get_segment_provider(SnapshotSegment::Transactions, 0, None)
+            .unwrap()
+            .with_auxiliar(
+                manager.get_segment_provider(SnapshotSegment::BlockBodyIndices, 10, None).unwrap(),
+            );
+
+        // Empty and inverted ranges, both within the jars and outside of them.
+        for (start, end) in [(15, 15), (15, 12), (0, 0), (50, 40), (u64::MAX, 0)] {
+            let (blocks, txs) = (start..end, start.saturating_add(90)..end.saturating_add(90));
+
+            assert!(headers.headers_range(blocks.clone()).unwrap().is_empty());
+            assert!(headers.headers_range_exact(blocks.clone()).unwrap().is_empty());
+            assert!(headers.headers_range_rev(blocks.clone()).unwrap().is_empty());
+            assert!(headers.sealed_headers_range(blocks.clone()).unwrap().is_empty());
+            assert!(headers.headers_td_range(blocks.clone()).unwrap().is_empty());
+            assert!(headers.headers_difficulty_range(blocks.clone()).unwrap().is_empty());
+            assert!(headers.headers_iter(blocks.clone()).unwrap().next().is_none());
+            assert!(headers.canonical_hashes_range(start, end).unwrap().is_empty());
+            assert!(headers.canonical_hashes_strided(start, end, 3, true).unwrap().is_empty());
+
+            assert!(transactions.transactions_by_tx_range(txs.clone()).unwrap().is_empty());
+            assert!(transactions.transactions_by_tx_range_rev(txs.clone()).unwrap().is_empty());
+            assert!(transactions.transactions_iter(txs.clone()).unwrap().next().is_none());
+            assert!(transactions.senders_by_tx_range(txs.clone()).unwrap().is_empty());
+            assert!(transactions.transactions_by_block_range(blocks.clone()).unwrap().is_empty());
+
+            assert!(receipts.receipts_by_tx_range(txs.clone()).unwrap().is_empty());
+            assert!(receipts.receipts_iter(txs.clone()).unwrap().next().is_none());
+            assert!(receipts.receipts_by_tx_range_filter(txs, |_| true).unwrap().is_empty());
+        }
+
+        // Still answered as usual otherwise.
+        assert_eq!(
+            transactions.transactions_by_block_range(10..12).unwrap(),
+            vec![
+                transactions.transactions_by_tx_range(100..104).unwrap(),
+                transactions.transactions_by_tx_range(104..110).unwrap(),
+            ]
+            .into_iter()
+            .map(|txs| txs.into_iter().map(|tx| tx.with_hash()).collect::<Vec<_>>())
+            .collect::<Vec<_>>()
+        );
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn metrics_advance() {
+        use metrics_util::{
+            debugging::{DebugValue, DebuggingRecorder},
+            MetricKind,
+        };
+
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        recorder.install().unwrap();
+
+        // Other tests may query jars of the same segment concurrently, so counters are only
+        // checked to have advanced at least by the queries of this one.
+        let counter = |name: &str| {
+            snapshotter
+                .snapshot()
+                .into_vec()
+                .into_iter()
+                .find_map(|(key, _, _, value)| {
+                    let matches = key.kind() == MetricKind::Counter &&
+                        key.key().name() == format!("snapshots.jar.{name}") &&
+                        key.key().labels().any(|label| label.value() == "Receipts");
+                    match value {
+                        DebugValue::Counter(value) if matches => Some(value),
+                        _ => None,
+                    }
+                })
+                .unwrap_or_default()
+        };
+
+        let snap_file = tempfile::NamedTempFile::new().unwrap();
+        create_receipts_jar(snap_file.path(), 100..=109);
+        let manager = SnapshotProvider::default();
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Receipts, 0, Some(snap_file.path().into()))
+            .unwrap();
+
+        let before = ["point_queries", "range_queries", "rows_decoded", "cursors"].map(counter);
+
+        // Two point queries, a range query of five rows and an iterator over three of them, each
+        // with its own cursor. Rows read by the iterator are recorded once it's dropped.
+        jar.receipt(100).unwrap();
+        jar.receipt(105).unwrap();
+        jar.receipts_by_tx_range(102..107).unwrap();
+        assert_eq!(jar.receipts_iter(100..).unwrap().take(3).count(), 3);
+
+        let after = ["point_queries", "range_queries", "rows_decoded", "cursors"].map(counter);
+        for ((before, after), advanced) in before.iter().zip(after).zip([2, 2, 10, 4]) {
+            assert!(after >= before + advanced);
+        }
+    }
+
+    #[test]
+    fn try_with_auxiliar_compatibility() {
+        let headers_file = tempfile::NamedTempFile::new().unwrap();
+        let receipts_file = tempfile::NamedTempFile::new().unwrap();
+        let more_receipts_file = tempfile::NamedTempFile::new().unwrap();
+        let transactions_file = tempfile::NamedTempFile::new().unwrap();
+        let bodies_file = tempfile::NamedTempFile::new().unwrap();
+        let other_bodies_file = tempfile::NamedTempFile::new().unwrap();
+        let mislabeled_file = tempfile::NamedTempFile::new().unwrap();
+        create_headers_jar(headers_file.path(), 0..=3);
+        create_receipts_jar(receipts_file.path(), 0..=5);
+        create_receipts_jar(more_receipts_file.path(), 6..=9);
+        create_transactions_jar(transactions_file.path(), 0..=5);
+        create_block_body_indices_jar(bodies_file.path(), 0, 0, &[2, 0, 3, 1]);
+        create_block_body_indices_jar(other_bodies_file.path(), 10, 6, &[2, 2]);
+        // Transactions stored as if they were headers.
+        create_jar(
+            mislabeled_file.path(),
+            SnapshotSegment::Headers,
+            0..=1,
+            0..=1,
+            vec![vec![vec![0], vec![1]]],
+            vec![],
+        );
+
+        // Jars of a segment share their manager key, so each one gets its own manager.
+        let managers = (0..7).map(|_| SnapshotProvider::default()).collect::<Vec<_>>();
+        let jar = |index: usize, segment, block, path: &Path| {
+            managers[index].get_segment_provider(segment, block, Some(path.into())).unwrap()
+        };
+        let receipts = || jar(0, SnapshotSegment::Receipts, 0, receipts_file.path());
+        let transactions = || jar(1, SnapshotSegment::Transactions, 0, transactions_file.path());
+        let bodies = || jar(2, SnapshotSegment::BlockBodyIndices, 0, bodies_file.path());
+        let more_receipts = || jar(3, SnapshotSegment::Receipts, 0, more_receipts_file.path());
+        let other_bodies =
+            || jar(4, SnapshotSegment::BlockBodyIndices, 10, other_bodies_file.path());
+        let headers = || jar(5, SnapshotSegment::Headers, 0, headers_file.path());
+        let mislabeled = || jar(6, SnapshotSegment::Headers, 0, mislabeled_file.path());
+
+        // Other segments of the same blocks, and jars extending the coverage of a segment.
+        let chained = receipts()
+            .try_with_auxiliar(transactions())
+            .unwrap()
+            .try_with_auxiliar(bodies())
+            .unwrap()
+            .try_with_auxiliar(more_receipts())
+            .unwrap();
+        assert_eq!(chained.receipts_by_block(2.into()).unwrap().map(|r| r.len()), Some(3));
+        assert!(headers().try_with_auxiliar(bodies()).is_ok());
+
+        let assert_incompatible = |result: RethResult<SnapshotJarProvider<'_>>| {
+            assert_matches!(
+                result,
+                Err(RethError::Provider(ProviderError::IncompatibleSnapshotAuxiliar { .. }))
+            )
+        };
+
+        // Overlapping jars of the same segment, either the main one or a previous auxiliar.
+        assert_incompatible(receipts().try_with_auxiliar(receipts()));
+        assert_incompatible(
+            receipts().try_with_auxiliar(bodies()).unwrap().try_with_auxiliar(bodies()),
+        );
+        // Data of other blocks.
+        assert_incompatible(headers().try_with_auxiliar(other_bodies()));
+        // Metadata not matching the data.
+        assert_incompatible(receipts().try_with_auxiliar(mislabeled()));
+    }
+
+    #[test]
+    fn block_bodies_iter_matches_transactions_by_block() {
+        let transactions_file = tempfile::NamedTempFile::new().unwrap();
+        let bodies_file = tempfile::NamedTempFile::new().unwrap();
+        create_transactions_jar(transactions_file.path(), 0..=5);
+        // The last block references transactions outside of the transactions jar.
+        create_block_body_indices_jar(bodies_file.path(), 0, 0, &[2, 0, 3, 1, 2]);
+
+        let manager = SnapshotProvider::default();
+        load_jars(
+            &manager,
+            &[
+                (SnapshotSegment::Transactions, 0, transactions_file.path()),
+                (SnapshotSegment::BlockBodyIndices, 0, bodies_file.path()),
+            ],
+        );
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Transactions, 0, None)
+            .unwrap()
+            .with_auxiliar(
+                manager.get_segment_provider(SnapshotSegment::BlockBodyIndices, 0, None).unwrap(),
+            );
+
+        // Bodies are read lazily, one block at a time.
+        let mut bodies = jar.block_bodies_iter(0..).unwrap();
+        for number in 0..4 {
+            let (body_number, transactions) = bodies.next().unwrap().unwrap();
+            assert_eq!(body_number, number);
+            assert_eq!(Some(transactions), jar.transactions_by_block(number.into()).unwrap());
+        }
+        assert_matches!(
+            bodies.next(),
+            Some(Err(RethError::Provider(ProviderError::SnapshotOutOfRange { .. })))
+        );
+        assert_matches!(
+            jar.transactions_by_block(4.into()),
+            Err(RethError::Provider(ProviderError::SnapshotOutOfRange { .. }))
+        );
+        // It ends at the first block missing from the block body indices jar.
+        assert!(bodies.next().is_none());
+
+        assert_eq!(
+            jar.block_bodies_iter(1..3)
+                .unwrap()
+                .map(|body| body.unwrap().1.len())
+                .collect::<Vec<_>>(),
+            vec![0, 3]
+        );
+
+        // Both segments are required.
+        let transactions = manager.get_segment_provider(SnapshotSegment::Transactions, 0, None);
+        assert_matches!(
+            transactions.unwrap().block_bodies_iter(0..4).map(|_| ()),
+            Err(RethError::Provider(ProviderError::UnsupportedProvider))
+        );
+    }
+
+    #[test]
+    fn scan_from_resumes() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let transactions = create_transactions_jar(file.path(), 10..=19)
+            .into_iter()
+            .map(TransactionSignedNoHash::from)
+            .collect::<Vec<_>>();
+
+        let manager = SnapshotProvider::default();
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Transactions, 0, Some(file.path().into()))
+            .unwrap();
+        assert_eq!(jar.tx_range(), 10..20);
+        let scan = |start| jar.scan_from(start).unwrap().collect::<RethResult<Vec<_>>>().unwrap();
+
+        // Resuming after the last transaction processed, like after a restart.
+        for start in 10..20 {
+            let expected = (start..20).zip(transactions[(start - 10) as usize..].iter().cloned());
+            assert_eq!(scan(start), expected.collect::<Vec<_>>());
+        }
+        // Starting before the jar starts at its first transaction.
+        assert_eq!(scan(0).first().map(|(num, _)| *num), Some(10));
+        // Nothing is left at or beyond its upper bound.
+        assert!(scan(20).is_empty());
+        assert!(scan(u64::MAX).is_empty());
+
+        // Cursors can seek back to a number and read from it.
+        let mut cursor = jar.cursor().unwrap();
+        cursor.seek(15);
+        assert_eq!(cursor.number(), 15);
+        assert_eq!(
+            cursor.next_one::<TransactionMask<TransactionSignedNoHash>>().unwrap(),
+            Some((15, transactions[5].clone()))
+        );
+        cursor.seek(12);
+        assert_eq!(
+            cursor.next_one::<TransactionMask<TransactionSignedNoHash>>().unwrap(),
+            Some((12, transactions[2].clone()))
+        );
+        assert_eq!(cursor.number(), 13);
+    }
+
+    #[test]
+    fn tx_and_receipt_by_tx_range_alignment() {
+        let transactions_file = tempfile::NamedTempFile::new().unwrap();
+        let receipts_file = tempfile::NamedTempFile::new().unwrap();
+        let truncated_receipts_file = tempfile::NamedTempFile::new().unwrap();
+        let transactions = create_transactions_jar(transactions_file.path(), 0..=7)
+            .into_iter()
+            .map(TransactionSignedNoHash::from)
+            .collect::<Vec<_>>();
+        let receipts = create_receipts_jar(receipts_file.path(), 2..=5);
+        // Covers the same transactions according to its metadata, but holds only two of them.
+        create_jar(
+            truncated_receipts_file.path(),
+            SnapshotSegment::Receipts,
+            0..=0,
+            2..=5,
+            vec![receipts[..2].iter().map(|receipt| receipt.clone().compress()).collect()],
+            vec![],
+        );
+
+        // Jars of a segment share their manager key, so each receipts jar gets its own manager.
+        let (manager, truncated_manager) =
+            (SnapshotProvider::default(), SnapshotProvider::default());
+        load_jars(
+            &manager,
+            &[
+                (SnapshotSegment::Transactions, 0, transactions_file.path()),
+                (SnapshotSegment::Receipts, 0, receipts_file.path()),
+            ],
+        );
+        load_jars(
+            &truncated_manager,
+            &[(SnapshotSegment::Receipts, 0, truncated_receipts_file.path())],
+        );
+        let transactions_jar =
+            || manager.get_segment_provider(SnapshotSegment::Transactions, 0, None).unwrap();
+        let jar = transactions_jar().with_auxiliar(
+            manager.get_segment_provider(SnapshotSegment::Receipts, 0, None).unwrap(),
+        );
+
+        let expected = |range: Range<usize>| {
+            transactions[range.clone()]
+                .iter()
+                .cloned()
+                .zip(receipts[range.start - 2..range.end - 2].iter().cloned())
+                .collect::<Vec<_>>()
+        };
+        // Only the transactions with receipts are returned.
+        assert_eq!(jar.tx_and_receipt_by_tx_range(..).unwrap(), expected(2..6));
+        assert_eq!(jar.tx_and_receipt_by_tx_range(3..5).unwrap(), expected(3..5));
+        assert_matches!(
+            jar.tx_and_receipt_by_tx_range(0..2),
+            Err(RethError::Provider(ProviderError::SnapshotOutOfRange {
+                segment: SnapshotSegment::Receipts,
+                ..
+            }))
+        );
+
+        // Both segments are required.
+        assert_matches!(
+            transactions_jar().tx_and_receipt_by_tx_range(..),
+            Err(RethError::Provider(ProviderError::UnsupportedProvider))
+        );
+
+        let jar = transactions_jar().with_auxiliar(
+            truncated_manager.get_segment_provider(SnapshotSegment::Receipts, 0, None).unwrap(),
+        );
+        assert_eq!(jar.tx_and_receipt_by_tx_range(..4).unwrap(), expected(2..4));
+        assert_matches!(
+            jar.tx_and_receipt_by_tx_range(..),
+            Err(RethError::Provider(ProviderError::SnapshotSegmentsMisaligned {
+                number: 4,
+                present: SnapshotSegment::Transactions,
+                missing: SnapshotSegment::Receipts,
+            }))
+        );
+    }
+
+    #[test]
+    fn headers_range_par_matches_sequential() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        create_headers_jar(file.path(), 0..=99);
+
+        let manager = SnapshotProvider::default();
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Headers, 0, Some(file.path().into()))
+            .unwrap();
+
+        // Narrow ranges are read sequentially.
+        assert_eq!(jar.headers_range_par(..).unwrap(), jar.headers_range(..).unwrap());
+
+        // Chunks of a single block, chunks not dividing the range evenly and a single chunk all
+        // match it, also for ranges going beyond the end of the jar.
+        for range in [0..100, 10..58, 90..200, 5..5] {
+            let expected = jar.headers_range(range.clone()).unwrap();
+            for chunk_size in [1, 7, 33, 100, 1000] {
+                let present = jar.query_range(range.clone()).unwrap();
+                assert_eq!(jar.headers_range_in_chunks(present, chunk_size).unwrap(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn batched_hash_lookups_match_singular() {
+        let headers_file = tempfile::NamedTempFile::new().unwrap();
+        let transactions_file = tempfile::NamedTempFile::new().unwrap();
+        let headers = create_headers_jar(headers_file.path(), 10..=19);
+        let transactions = create_transactions_jar(transactions_file.path(), 20..=29);
+
+        let manager = SnapshotProvider::default();
+        load_jars(
+            &manager,
+            &[
+                (SnapshotSegment::Headers, 10, headers_file.path()),
+                (SnapshotSegment::Transactions, 0, transactions_file.path()),
+            ],
+        );
+        let headers_jar = manager.get_segment_provider(SnapshotSegment::Headers, 10, None).unwrap();
+        let transactions_jar =
+            manager.get_segment_provider(SnapshotSegment::Transactions, 0, None).unwrap();
+
+        let block_hashes = [
+            headers[4].hash(),
+            B256::random(),
+            headers[0].hash(),
+            headers[4].hash(),
+            transactions[0].hash(),
+            headers[9].hash(),
+        ];
+        assert_eq!(
+            headers_jar.block_numbers(&block_hashes).unwrap(),
+            vec![Some(14), None, Some(10), Some(14), None, Some(19)]
+        );
+        assert_eq!(
+            headers_jar.block_numbers(&block_hashes).unwrap(),
+            block_hashes
+                .iter()
+                .map(|hash| headers_jar.block_number(*hash).unwrap())
+                .collect::<Vec<_>>()
+        );
+
+        let tx_hashes = [
+            B256::random(),
+            transactions[9].hash(),
+            transactions[3].hash(),
+            headers[3].hash(),
+            transactions[3].hash(),
+        ];
+        assert_eq!(
+            transactions_jar.transaction_ids(&tx_hashes).unwrap(),
+            vec![None, Some(29), Some(23), None, Some(23)]
+        );
+        assert_eq!(
+            transactions_jar.transaction_ids(&tx_hashes).unwrap(),
+            tx_hashes
+                .iter()
+                .map(|hash| transactions_jar.transaction_id(*hash).unwrap())
+                .collect::<Vec<_>>()
+        );
+
+        assert_eq!(headers_jar.block_numbers(&[]).unwrap(), vec![]);
+        assert_eq!(transactions_jar.transaction_ids(&[]).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn verify_timestamp_monotonicity_violations() {
+        let snap_file = tempfile::NamedTempFile::new().unwrap();
+        let timestamps = [100, 101, 101, 99, 105, 106];
+        create_headers_jar_from(
+            snap_file.path(),
+            timestamps
+                .iter()
+                .zip(10..)
+                .map(|(&timestamp, number)| {
+                    Header { number, timestamp, ..Default::default() }.seal_slow()
+                })
+                .collect(),
+        );
+
+        let manager = SnapshotProvider::default();
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Headers, 10, Some(snap_file.path().into()))
+            .unwrap();
+
+        // Equal timestamps are only violations if strict, unlike decreasing ones. Ranges starting
+        // before the jar are verified from its first header.
+        assert_eq!(jar.verify_timestamp_monotonicity(.., true).unwrap(), vec![12, 13]);
+        assert_eq!(jar.verify_timestamp_monotonicity(.., false).unwrap(), vec![13]);
+        assert_eq!(jar.verify_timestamp_monotonicity(5..=12, true).unwrap(), vec![12]);
+
+        // A range starting inside the jar is compared against the parent of its first block.
+        assert_eq!(jar.verify_timestamp_monotonicity(13.., false).unwrap(), vec![13]);
+        assert_eq!(jar.verify_timestamp_monotonicity(14.., true).unwrap(), vec![]);
+
+        // Ranges outside the jar are not reported as passing.
+        assert_matches!(
+            jar.verify_timestamp_monotonicity(0..10, true),
+            Err(RethError::Provider(ProviderError::SnapshotOutOfRange { .. }))
+        );
+    }
+
+    #[test]
+    fn effective_tips_by_transaction_type() {
+        let headers_file = tempfile::NamedTempFile::new().unwrap();
+        let transactions_file = tempfile::NamedTempFile::new().unwrap();
+        let bodies_file = tempfile::NamedTempFile::new().unwrap();
+        let (headers, transactions, tx_counts) = fee_blocks();
+        let headers = create_headers_jar_from(headers_file.path(), headers);
+        create_transactions_jar_from(transactions_file.path(), 0..=4, transactions);
+        create_block_body_indices_jar(bodies_file.path(), 10, 0, &tx_counts);
+
+        let manager = SnapshotProvider::default();
+        load_jars(
+            &manager,
+            &[
+                (SnapshotSegment::Headers, 10, headers_file.path()),
+                (SnapshotSegment::Transactions, 0, transactions_file.path()),
+                (SnapshotSegment::BlockBodyIndices, 10, bodies_file.path()),
+            ],
+        );
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Headers, 10, None)
+            .unwrap()
+            .with_auxiliar(
+                manager.get_segment_provider(SnapshotSegment::Transactions, 0, None).unwrap(),
+            )
+            .with_auxiliar(
+                manager.get_segment_provider(SnapshotSegment::BlockBodyIndices, 10, None).unwrap(),
+            );
+
+        // Legacy and EIP-2930 tips are `gas_price - base_fee`, while EIP-1559 ones are the
+        // priority fee, capped by `max_fee - base_fee`.
+        assert_eq!(jar.effective_tips_by_block(10.into()).unwrap(), Some(vec![15, 2, 5, 3]));
+        assert_eq!(
+            jar.effective_tips_by_block(headers[0].hash().into()).unwrap(),
+            Some(vec![15, 2, 5, 3])
+        );
+        // Without a base fee, the whole gas price is the tip.
+        assert_eq!(jar.effective_tips_by_block(11.into()).unwrap(), Some(vec![7]));
+        assert_eq!(jar.effective_tips_by_block(12.into()).unwrap(), Some(vec![]));
+
+        // Unknown hashes are not found, while blocks outside the jars have to be queried on others.
+        assert_eq!(jar.effective_tips_by_block(B256::random().into()).unwrap(), None);
+        assert_matches!(
+            jar.effective_tips_by_block(13.into()),
+            Err(RethError::Provider(ProviderError::SnapshotOutOfRange { .. }))
+        );
+    }
+
+    #[test]
+    fn is_block_empty_from_body_indices() {
+        let snap_file = tempfile::NamedTempFile::new().unwrap();
+        create_block_body_indices_jar(snap_file.path(), 10, 5, &[2, 0, 1]);
+
+        let manager = SnapshotProvider::default();
+        let jar = manager
+            .get_segment_provider(
+                SnapshotSegment::BlockBodyIndices,
+                10,
+                Some(snap_file.path().into()),
+            )
+            .unwrap();
+
+        assert_eq!(jar.is_block_empty(10.into()).unwrap(), Some(false));
+        assert_eq!(jar.is_block_empty(11.into()).unwrap(), Some(true));
+        assert_eq!(jar.is_block_empty(12.into()).unwrap(), Some(false));
+
+        // Blocks outside the jar have to be queried on another one.
+        for number in [9u64, 13] {
+            assert_matches!(
+                jar.is_block_empty(number.into()),
+                Err(RethError::Provider(ProviderError::SnapshotOutOfRange { .. }))
+            );
+        }
+    }
+
+    #[test]
+    fn block_total_fees_from_receipts() {
+        let headers_file = tempfile::NamedTempFile::new().unwrap();
+        let transactions_file = tempfile::NamedTempFile::new().unwrap();
+        let receipts_file = tempfile::NamedTempFile::new().unwrap();
+        let bodies_file = tempfile::NamedTempFile::new().unwrap();
+        let (headers, transactions, tx_counts) = fee_blocks();
+        create_headers_jar_from(headers_file.path(), headers);
+        create_transactions_jar_from(transactions_file.path(), 0..=4, transactions);
+        create_block_body_indices_jar(bodies_file.path(), 10, 0, &tx_counts);
+        // Cumulative within each block, with a transaction using no gas.
+        create_receipts_jar_from(
+            receipts_file.path(),
+            0..=4,
+            [21_000, 50_000, 50_000, 80_000, 21_000]
+                .into_iter()
+                .map(|cumulative_gas_used| Receipt { cumulative_gas_used, ..Default::default() })
+                .collect(),
+        );
+
+        let manager = SnapshotProvider::default();
+        load_jars(
+            &manager,
+            &[
+                (SnapshotSegment::Headers, 10, headers_file.path()),
+                (SnapshotSegment::Transactions, 0, transactions_file.path()),
+                (SnapshotSegment::Receipts, 0, receipts_file.path()),
+                (SnapshotSegment::BlockBodyIndices, 10, bodies_file.path()),
+            ],
+        );
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Headers, 10, None)
+            .unwrap()
+            .with_auxiliar(
+                manager.get_segment_provider(SnapshotSegment::Transactions, 0, None).unwrap(),
+            )
+            .with_auxiliar(
+                manager.get_segment_provider(SnapshotSegment::Receipts, 0, None).unwrap(),
+            )
+            .with_auxiliar(
+                manager.get_segment_provider(SnapshotSegment::BlockBodyIndices, 10, None).unwrap(),
+            );
+
+        // Gas used of 21000, 29000, 0 and 30000 at effective gas prices of 25, 12, 15 and 13.
+        assert_eq!(
+            jar.block_total_fees(10.into()).unwrap(),
+            Some(U256::from(21_000 * 25 + 29_000 * 12 + 30_000 * 13))
+        );
+        assert_eq!(jar.block_total_fees(11.into()).unwrap(), Some(U256::from(21_000 * 7)));
+        assert_eq!(jar.block_total_fees(12.into()).unwrap(), Some(U256::ZERO));
+        assert_matches!(
+            jar.block_total_fees(9.into()),
+            Err(RethError::Provider(ProviderError::SnapshotOutOfRange { .. }))
+        );
+    }
+
+    #[test]
+    fn verify_cumulative_gas_monotonicity_violations() {
+        let receipts_file = tempfile::NamedTempFile::new().unwrap();
+        let bodies_file = tempfile::NamedTempFile::new().unwrap();
+        create_block_body_indices_jar(bodies_file.path(), 10, 5, &[3, 0, 2]);
+        create_receipts_jar_from(
+            receipts_file.path(),
+            5..=9,
+            [100, 50, 60, 30, 20]
+                .into_iter()
+                .map(|cumulative_gas_used| Receipt { cumulative_gas_used, ..Default::default() })
+                .collect(),
+        );
+
+        let manager = SnapshotProvider::default();
+        load_jars(
+            &manager,
+            &[
+                (SnapshotSegment::Receipts, 0, receipts_file.path()),
+                (SnapshotSegment::BlockBodyIndices, 10, bodies_file.path()),
+            ],
+        );
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Receipts, 0, None)
+            .unwrap()
+            .with_auxiliar(
+                manager.get_segment_provider(SnapshotSegment::BlockBodyIndices, 10, None).unwrap(),
+            );
+
+        // Cumulative gas restarts with each block, and the empty block in between has nothing to
+        // verify. Ranges starting before the jar are verified from its first block.
+        assert_eq!(jar.verify_cumulative_gas_monotonicity(..).unwrap(), vec![(10, 6), (12, 9)]);
+        assert_eq!(jar.verify_cumulative_gas_monotonicity(11..=11).unwrap(), vec![]);
+        assert_eq!(jar.verify_cumulative_gas_monotonicity(11..).unwrap(), vec![(12, 9)]);
+
+        // Ranges outside the jar are not reported as passing.
+        assert_matches!(
+            jar.verify_cumulative_gas_monotonicity(0..10),
+            Err(RethError::Provider(ProviderError::SnapshotOutOfRange { .. }))
+        );
+    }
+
+    #[test]
+    fn transactions_from_bounds() {
+        let snap_file = tempfile::NamedTempFile::new().unwrap();
+        let transactions = create_transactions_jar(snap_file.path(), 10..=19)
+            .into_iter()
+            .map(TransactionSignedNoHash::from)
+            .collect::<Vec<_>>();
+
+        let manager = SnapshotProvider::default();
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Transactions, 0, Some(snap_file.path().into()))
+            .unwrap();
+        let numbers = |start, count| {
+            jar.transactions_from(start, count)
+                .unwrap()
+                .into_iter()
+                .map(|(num, _)| num)
+                .collect::<Vec<_>>()
+        };
+
+        // Starting before the jar starts at its first transaction, and the jar end cuts it short.
+        assert_eq!(numbers(0, 3), vec![10, 11, 12]);
+        assert_eq!(numbers(17, 5), vec![17, 18, 19]);
+        assert_eq!(jar.transactions_from(12, 1).unwrap(), vec![(12, transactions[2].clone())]);
+        assert_eq!(numbers(12, 0), vec![]);
+
+        // Starting after the jar has to be queried on another one.
+        assert_matches!(
+            jar.transactions_from(20, 5),
+            Err(RethError::Provider(ProviderError::SnapshotOutOfRange { .. }))
+        );
+    }
+
+    #[test]
+    fn receipts_with_log_matching_after_first_tx() {
+        let snap_file = tempfile::NamedTempFile::new().unwrap();
+        let (address, other_address) = (Address::random(), Address::random());
+        let log = |address| Log { address, topics: vec![], data: Bytes::default() };
+        let receipts = create_receipts_jar_from(
+            snap_file.path(),
+            5..=9,
+            [vec![log(address)], vec![], vec![log(other_address)], vec![log(address)], vec![]]
+                .map(|logs| Receipt { logs, ..Default::default() })
+                .to_vec(),
+        );
+        let receipt = |num: TxNumber| (num, receipts[num as usize - 5].clone());
+
+        let manager = SnapshotProvider::default();
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Receipts, 0, Some(snap_file.path().into()))
+            .unwrap();
+
+        // Unbounded ranges start at the first receipt of the jar rather than at 0.
+        assert_eq!(
+            jar.receipts_matching_range(.., &[], &[]).unwrap(),
+            vec![receipt(5), receipt(7), receipt(8)]
+        );
+        assert_eq!(
+            jar.receipts_with_log_address_by_tx_range(address, ..).unwrap(),
+            vec![receipt(5), receipt(8)]
+        );
+        assert_eq!(
+            jar.receipts_matching_range(6.., &[], &[]).unwrap(),
+            vec![receipt(7), receipt(8)]
+        );
+        assert_matches!(
+            jar.receipts_matching_range(..5, &[], &[]),
+            Err(RethError::Provider(ProviderError::SnapshotOutOfRange { .. }))
+        );
+    }
+
+    #[test]
+    fn block_bodies_iter_after_first_block() {
+        let transactions_file = tempfile::NamedTempFile::new().unwrap();
+        let bodies_file = tempfile::NamedTempFile::new().unwrap();
+        // Covers transactions 100..=106, but only the first four are present.
+        let mut rng = generators::rng();
+        let transactions = create_transactions_jar_from(
+            transactions_file.path(),
+            100..=106,
+            (0..4).map(|_| random_signed_tx(&mut rng)).collect(),
+        );
+        // Block 13 has one of its transactions present, and block 14 none of them.
+        create_block_body_indices_jar(bodies_file.path(), 10, 100, &[2, 0, 1, 2, 2]);
+
+        let manager = SnapshotProvider::default();
+        load_jars(
+            &manager,
+            &[
+                (SnapshotSegment::Transactions, 0, transactions_file.path()),
+                (SnapshotSegment::BlockBodyIndices, 10, bodies_file.path()),
+            ],
+        );
+        let jar = manager
+            .get_segment_provider(SnapshotSegment::Transactions, 0, None)
+            .unwrap()
+            .with_auxiliar(
+                manager.get_segment_provider(SnapshotSegment::BlockBodyIndices, 10, None).unwrap(),
+            );
+        let expected = vec![transactions[..2].to_vec(), vec![], transactions[2..3].to_vec()];
+
+        // Unbounded ranges start at the first block of the jar rather than at 0.
+        let mut bodies = jar.block_bodies_iter(..).unwrap();
+        for (number, transactions) in (10..).zip(&expected) {
+            assert_eq!(bodies.next().unwrap().unwrap(), (number, transactions.clone()));
+        }
+        assert_matches!(
+            bodies.next(),
+            Some(Err(RethError::Provider(ProviderError::BlockBodyTransactionCount)))
+        );
+        assert!(bodies.next().is_none());
+        assert_eq!(jar.transactions_by_block_range(..13).unwrap(), expected);
+
+        // Partially and fully missing transactions behave as they do for a single block.
+        assert_matches!(
+            jar.transactions_by_block(13.into()),
+            Err(RethError::Provider(ProviderError::BlockBodyTransactionCount))
+        );
+        assert_matches!(
+            jar.transactions_by_block_range(..),
+            Err(RethError::Provider(ProviderError::BlockBodyTransactionCount))
+        );
+        assert_eq!(jar.transactions_by_block(14.into()).unwrap(), None);
+        assert!(jar.block_bodies_iter(14..).unwrap().next().is_none());
+
+        assert_matches!(
+            jar.block_bodies_iter(..10).map(|_| ()),
+            Err(RethError::Provider(ProviderError::SnapshotOutOfRange { .. }))
+        );
+        assert_matches!(
+            jar.transactions_by_block_range(15..20),
+            Err(RethError::Provider(ProviderError::SnapshotOutOfRange { .. }))
+        );
+    }
+
+    #[test]
+    fn transactions_stored_size() {
+        let transactions_file = tempfile::NamedTempFile::new().unwrap();
+        let receipts_file = tempfile::NamedTempFile::new().unwrap();
+        let sizes = create_transactions_jar(transactions_file.path(), 10..=14)
+            .into_iter()
+            .map(|tx| TransactionSignedNoHash::from(tx).compress().len() as u64)
+            .collect::<Vec<_>>();
+        create_receipts_jar(receipts_file.path(), 10..=14);
+
+        let manager = SnapshotProvider::default();
+        load_jars(
+            &manager,
+            &[
+                (SnapshotSegment::Transactions, 0, transactions_file.path()),
+                (SnapshotSegment::Receipts, 0, receipts_file.path()),
+            ],
+        );
+        let transactions =
+            || manager.get_segment_provider(SnapshotSegment::Transactions, 0, None).unwrap();
+        let receipts = || manager.get_segment_provider(SnapshotSegment::Receipts, 0, None).unwrap();
+
+        // Without compression, values are stored as they're encoded.
+        let jar = transactions();
+        assert_eq!(jar.transactions_stored_size_by_tx_range(..).unwrap(), sizes);
+        assert_eq!(jar.transactions_stored_size_by_tx_range(12..100).unwrap(), &sizes[2..]);
+        assert_eq!(jar.transaction_stored_size_by_id(13).unwrap(), Some(sizes[3]));
+        assert_matches!(
+            jar.transaction_stored_size_by_id(15),
+            Err(RethError::Provider(ProviderError::SnapshotOutOfRange { .. }))
+        );
+
+        // Other jars don't hold transactions by themselves, only through an auxiliar.
+        assert_matches!(
+            receipts().transactions_stored_size_by_tx_range(..),
+            Err(RethError::Provider(ProviderError::UnsupportedProvider))
+        );
+        let jar = receipts().with_auxiliar(transactions());
+        assert_eq!(jar.transactions_stored_size_by_tx_range(..).unwrap(), sizes);
+        assert_eq!(jar.transaction_stored_size_by_id(10).unwrap(), Some(sizes[0]));
+    }
 }
@@ -8,14 +8,24 @@ use reth_primitives::{
     Header, SealedHeader, SnapshotSegment, TransactionMeta, TransactionSigned,
     TransactionSignedNoHash, TxHash, TxNumber, B256, U256,
 };
-use std::{ops::RangeBounds, path::PathBuf};
+use std::{
+    ops::RangeBounds,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
 
 /// SnapshotProvider
 #[derive(Debug, Default)]
 pub struct SnapshotProvider {
     /// Maintains a map which allows for concurrent access to different `NippyJars`, over different
-    /// segments and ranges.
-    map: DashMap<(BlockNumber, SnapshotSegment), LoadedJar>,
+    /// segments and ranges. Providers share the jar of their entry, so that it can be replaced
+    /// while they're alive.
+    map: DashMap<(BlockNumber, SnapshotSegment), Arc<LoadedJar>>,
+    /// Epoch given to the next loaded jar.
+    next_epoch: AtomicU64,
 }
 
 impl SnapshotProvider {
@@ -31,11 +41,12 @@ impl SnapshotProvider {
         let key = (snapshot, segment);
 
         if let Some(jar) = self.map.get(&key) {
-            return Ok(jar.into())
+            return Ok(jar.clone().into())
         }
 
         if let Some(path) = &path {
-            self.map.insert(key, LoadedJar::new(NippyJar::load(path)?)?);
+            self.map
+                .insert(key, Arc::new(LoadedJar::new(NippyJar::load(path)?, self.next_epoch())?));
         } else {
             path = Some(segment.filename(
                 &((snapshot * BLOCKS_PER_SNAPSHOT)..=((snapshot + 1) * BLOCKS_PER_SNAPSHOT - 1)),
@@ -44,6 +55,36 @@ impl SnapshotProvider {
 
         self.get_segment_provider(segment, block, path)
     }
+
+    /// Loads the jar at `path` in place of the one serving the requested segment and range, under
+    /// a new epoch.
+    ///
+    /// Providers obtained before keep serving the replaced jar without blocking the reload, and
+    /// [`Self::is_stale`] reports their epoch as stale.
+    pub fn reload_segment_provider(
+        &self,
+        segment: SnapshotSegment,
+        block: BlockNumber,
+        path: &Path,
+    ) -> RethResult<SnapshotJarProvider<'_>> {
+        let key = (block / BLOCKS_PER_SNAPSHOT, segment);
+        self.map.insert(key, Arc::new(LoadedJar::new(NippyJar::load(path)?, self.next_epoch())?));
+
+        self.get_segment_provider(segment, block, None)
+    }
+
+    /// Returns `true` if data obtained from a provider of the requested segment and range at
+    /// `epoch` is stale, because its jar has since been reloaded or it's no longer loaded.
+    pub fn is_stale(&self, segment: SnapshotSegment, block: BlockNumber, epoch: u64) -> bool {
+        self.map
+            .get(&(block / BLOCKS_PER_SNAPSHOT, segment))
+            .map_or(true, |jar| jar.epoch() != epoch)
+    }
+
+    /// Returns a new epoch for a loaded jar.
+    fn next_epoch(&self) -> u64 {
+        self.next_epoch.fetch_add(1, Ordering::Relaxed)
+    }
 }
 
 impl HeaderProvider for SnapshotProvider {
@@ -0,0 +1,79 @@
+#[cfg(feature = "metrics")]
+use reth_metrics::{
+    metrics::{self, Counter, Histogram},
+    Metrics,
+};
+use reth_primitives::SnapshotSegment;
+
+/// Query metrics of a snapshot jar, labeled by its segment.
+///
+/// Only recorded with the `metrics` feature. Without it, this is empty and recording does
+/// nothing.
+#[derive(Debug)]
+pub(crate) struct SnapshotJarMetrics {
+    #[cfg(feature = "metrics")]
+    metrics: SnapshotSegmentMetrics,
+}
+
+impl SnapshotJarMetrics {
+    /// Creates the metrics of a `segment` jar.
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    pub(crate) fn new(segment: SnapshotSegment) -> Self {
+        Self {
+            #[cfg(feature = "metrics")]
+            metrics: SnapshotSegmentMetrics::new_with_labels(&[(
+                "segment",
+                format!("{segment:?}"),
+            )]),
+        }
+    }
+
+    /// Records a query of a single block or transaction, by number or by hash.
+    #[inline]
+    pub(crate) fn record_point_query(&self) {
+        #[cfg(feature = "metrics")]
+        self.metrics.point_queries.increment(1);
+    }
+
+    /// Records a query of `size` blocks or transactions.
+    #[inline]
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    pub(crate) fn record_range_query(&self, size: u64) {
+        #[cfg(feature = "metrics")]
+        {
+            self.metrics.range_queries.increment(1);
+            self.metrics.range_query_size.record(size as f64);
+        }
+    }
+
+    /// Records `rows` rows read from the jar, by any query.
+    #[inline]
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    pub(crate) fn record_rows_decoded(&self, rows: usize) {
+        #[cfg(feature = "metrics")]
+        self.metrics.rows_decoded.increment(rows as u64);
+    }
+
+    /// Records the creation of a cursor.
+    #[inline]
+    pub(crate) fn record_cursor(&self) {
+        #[cfg(feature = "metrics")]
+        self.metrics.cursors.increment(1);
+    }
+}
+
+#[cfg(feature = "metrics")]
+#[derive(Metrics)]
+#[metrics(scope = "snapshots.jar")]
+struct SnapshotSegmentMetrics {
+    /// Number of queries of a single block or transaction
+    point_queries: Counter,
+    /// Number of queries of a range of blocks or transactions
+    range_queries: Counter,
+    /// Number of blocks or transactions requested by range queries, once clamped to the jar
+    range_query_size: Histogram,
+    /// Number of rows read from the jar, by any query
+    rows_decoded: Counter,
+    /// Number of cursors created
+    cursors: Counter,
+}
@@ -2,27 +2,42 @@ mod manager;
 pub use manager::SnapshotProvider;
 
 mod jar;
-pub use jar::SnapshotJarProvider;
+pub use jar::{SnapshotJarCursor, SnapshotJarProvider};
+
+mod metrics;
+use metrics::SnapshotJarMetrics;
 
 use reth_interfaces::RethResult;
 use reth_nippy_jar::NippyJar;
-use reth_primitives::{snapshot::SegmentHeader, SnapshotSegment};
+use reth_primitives::snapshot::SegmentHeader;
 use std::ops::Deref;
 
-/// Alias type for each specific `NippyJar`.
-type LoadedJarRef<'a> = dashmap::mapref::one::Ref<'a, (u64, SnapshotSegment), LoadedJar>;
-
 /// Helper type to reuse an associated snapshot mmap handle on created cursors.
 #[derive(Debug)]
 pub struct LoadedJar {
     jar: NippyJar<SegmentHeader>,
     mmap_handle: reth_nippy_jar::MmapHandle,
+    /// Generation of the jar within its manager. Changes whenever the jar is reloaded.
+    epoch: u64,
+    /// Query metrics of the jar.
+    metrics: SnapshotJarMetrics,
 }
 
 impl LoadedJar {
-    fn new(jar: NippyJar<SegmentHeader>) -> RethResult<Self> {
+    fn new(jar: NippyJar<SegmentHeader>, epoch: u64) -> RethResult<Self> {
         let mmap_handle = jar.open_data()?;
-        Ok(Self { jar, mmap_handle })
+        let metrics = SnapshotJarMetrics::new(jar.user_header().segment());
+        Ok(Self { jar, mmap_handle, epoch, metrics })
+    }
+
+    /// Returns the generation of the jar within its manager.
+    fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// Returns the query metrics of the jar.
+    fn metrics(&self) -> &SnapshotJarMetrics {
+        &self.metrics
     }
 
     /// Returns a clone of the mmap handle that can be used to instantiate a cursor.
@@ -53,7 +68,7 @@ mod test {
     };
     use reth_interfaces::test_utils::generators::{self, random_header_range};
     use reth_nippy_jar::NippyJar;
-    use reth_primitives::{BlockNumber, B256, MAINNET, U256};
+    use reth_primitives::{BlockNumber, SnapshotSegment, B256, MAINNET, U256};
 
     #[test]
     fn test_snap() {